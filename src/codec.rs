@@ -0,0 +1,134 @@
+use bytes::{Buf, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+use crate::dso::DsoMessage;
+use crate::error::{DecodeError, EncodeError};
+use crate::message::{Decoded, Message};
+use crate::tcp::{self, LENGTH_PREFIX};
+use crate::update::UpdateMessage;
+
+/// DNS over TCP framing for `tokio_util::codec::Framed`, decoding each length-prefixed
+/// message in the section layout its opcode calls for.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DnsCodec;
+
+#[derive(Debug)]
+pub enum CodecError {
+    Io(std::io::Error),
+    Decode(DecodeError),
+    Encode(EncodeError),
+}
+
+impl core::fmt::Display for CodecError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Io(cause)     => write!(f, "cannot use stream: {}", cause),
+            Self::Decode(cause) => write!(f, "cannot decode message: {}", cause),
+            Self::Encode(cause) => write!(f, "cannot encode message: {}", cause),
+        }
+    }
+}
+
+impl core::error::Error for CodecError {}
+
+impl core::convert::From<std::io::Error> for CodecError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl Decoder for DnsCodec {
+    type Item = Decoded;
+    type Error = CodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if src.len() < LENGTH_PREFIX {
+            return Ok(None);
+        }
+        let length = u16::from_be_bytes([src[0], src[1]]) as usize;
+        if src.len() < LENGTH_PREFIX + length {
+            src.reserve(LENGTH_PREFIX + length - src.len());
+            return Ok(None);
+        }
+        src.advance(LENGTH_PREFIX);
+        let frame = src.split_to(length);
+        Decoded::try_from(&frame[..]).map(Some).map_err(CodecError::Decode)
+    }
+}
+
+impl Encoder<Message> for DnsCodec {
+    type Error = CodecError;
+
+    fn encode(&mut self, item: Message, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        tcp::encode_to(dst, |sink| item.encode_to(sink)).map(drop).map_err(CodecError::Encode)
+    }
+}
+
+impl Encoder<UpdateMessage> for DnsCodec {
+    type Error = CodecError;
+
+    fn encode(&mut self, item: UpdateMessage, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        self.encode(Message::from(item), dst)
+    }
+}
+
+impl Encoder<DsoMessage> for DnsCodec {
+    type Error = CodecError;
+
+    fn encode(&mut self, item: DsoMessage, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        tcp::encode_to(dst, |sink| item.encode_to(sink)).map(drop).map_err(CodecError::Encode)
+    }
+}
+
+impl Encoder<Decoded> for DnsCodec {
+    type Error = CodecError;
+
+    fn encode(&mut self, item: Decoded, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        match item {
+            Decoded::Standard(message) => self.encode(message, dst),
+            Decoded::Update(message)   => self.encode(message, dst),
+            Decoded::Dso(message)      => self.encode(message, dst),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{Field, Section};
+    use crate::header::{id, opcode, Header};
+    use pretty_assertions::assert_eq;
+
+    fn query(id: u16) -> Message {
+        Message {
+            header:      Header::query(id::Id(id), opcode::Opcode::Query),
+            questions:   vec![],
+            answers:     vec![],
+            authorities: vec![],
+            additionals: vec![],
+        }
+    }
+
+    #[test]
+    fn it_decodes_pipelined_messages() {
+        let mut codec = DnsCodec;
+        let mut stream = BytesMut::new();
+        codec.encode(query(1), &mut stream).unwrap();
+        codec.encode(query(2), &mut stream).unwrap();
+        assert_eq!(&stream[..2], &[0x00, 0x0C]);
+
+        let mut src = stream.split_to(20);
+        assert!(matches!(codec.decode(&mut src), Ok(Some(Decoded::Standard(message))) if message == query(1)));
+        assert!(matches!(codec.decode(&mut src), Ok(None)), "should wait for the rest of the message");
+        src.extend_from_slice(&stream);
+        assert!(matches!(codec.decode(&mut src), Ok(Some(Decoded::Standard(message))) if message == query(2)));
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn it_reports_error_when_message_malformed() {
+        let mut src = BytesMut::from(&[0x00, 0x02, 0xAB, 0xCD][..]);
+        let error = DnsCodec.decode(&mut src).unwrap_err();
+        assert!(matches!(error, CodecError::Decode(DecodeError::Truncated { section: Section::Header, field: Field::Qr, offset: 2 })));
+        assert!(src.is_empty(), "should skip the malformed message");
+    }
+}
@@ -0,0 +1,147 @@
+use alloc::{vec, vec::Vec};
+use crate::reader::Reader;
+use crate::error::{self, DecodeError, EncodeError, Field, Section};
+use crate::header::{count, opcode, Header};
+use crate::sink::{self, Sink};
+
+pub mod push;
+pub mod session;
+pub mod tlv;
+
+const HEADER_LENGTH: usize = 12;
+
+/// A DNS Stateful Operations message (RFC 8490 §5.4): a header with all counts zero,
+/// followed by TLVs instead of the usual sections.
+#[derive(Debug, Clone)]
+#[cfg_attr(test, derive(PartialEq, Eq))]
+pub struct DsoMessage {
+    pub header:     Header,
+    /// The first TLV, which decides what a request asks for. Responses may omit it.
+    pub primary:    Option<tlv::Tlv>,
+    pub additional: Vec<tlv::Tlv>,
+}
+
+impl core::convert::TryFrom<&[u8]> for DsoMessage {
+    type Error = DecodeError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let header = Header::try_from(value)?;
+        if header.opcode != opcode::Opcode::DnsStatefulOperations {
+            return Err(error::invalid_value(Field::Opcode, 2, Into::<u8>::into(header.opcode) as u32));
+        }
+        let counts: [(Field, u16); 4] = [
+            (Field::QdZoCount, header.qd_zo_count.into()),
+            (Field::AnPrCount, header.an_pr_count.into()),
+            (Field::NsUpCount, header.ns_up_count.into()),
+            (Field::Arcount,   header.arcount.into()),
+        ];
+        for (i, (field, count)) in counts.into_iter().enumerate() {
+            if count != 0 {
+                return Err(error::invalid_value(field, 4 + 2 * i, count as u32));
+            }
+        }
+        let mut buffer = Reader::new(value);
+        buffer.set_position(HEADER_LENGTH);
+        let mut tlvs = vec![];
+        while buffer.position() < value.len() {
+            tlvs.push(tlv::Tlv::read_from(&mut buffer).map_err(|e| e.in_section(Section::Dso))?);
+        }
+        let mut tlvs = tlvs.into_iter();
+
+        Ok(Self { header, primary: tlvs.next(), additional: tlvs.collect() })
+    }
+}
+
+impl DsoMessage {
+    /// Encodes into `sink` with all counts zero, returning the number of bytes written.
+    pub fn encode_to(&self, sink: &mut impl Sink) -> Result<usize, EncodeError> {
+        let start = sink.written();
+        let header = Header {
+            qd_zo_count: count::QdZoCount(0),
+            an_pr_count: count::AnPrCount(0),
+            ns_up_count: count::NsUpCount(0),
+            arcount:     count::Arcount(0),
+            ..self.header
+        };
+        header.write_to(sink)?;
+        for tlv in self.primary.iter().chain(&self.additional) {
+            tlv.write_to(sink).map_err(|e| e.in_section(Section::Dso))?;
+        }
+
+        Ok(sink.written() - start)
+    }
+
+    /// Encodes into `buffer`, returning the number of bytes written.
+    pub fn encode_into(&self, buffer: &mut [u8]) -> Result<usize, EncodeError> {
+        sink::encode_into(buffer, |sink| self.encode_to(sink))
+    }
+}
+
+impl core::convert::TryInto<Vec<u8>> for DsoMessage {
+    type Error = EncodeError;
+
+    fn try_into(self) -> Result<Vec<u8>, Self::Error> {
+        let mut buffer = vec![];
+        self.encode_to(&mut buffer)?;
+
+        Ok(buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::id;
+    use pretty_assertions::assert_eq;
+
+    fn keepalive_request() -> Vec<u8> {
+        vec![
+            0x00, 0x2A,              // ID = 42
+            0b0011_0000, 0x00,       // QR = Query, OPCODE = DSO
+            0x00, 0x00,              // QDCOUNT = 0
+            0x00, 0x00,              // ANCOUNT = 0
+            0x00, 0x00,              // NSCOUNT = 0
+            0x00, 0x00,              // ARCOUNT = 0
+            0x00, 0x01, 0x00, 0x08,  // Keepalive TLV
+            0x00, 0x00, 0x3A, 0x98,
+            0x00, 0x00, 0xC3, 0x50,
+            0x00, 0x03, 0x00, 0x03,  // Encryption Padding TLV
+            0x00, 0x00, 0x00,
+        ]
+    }
+
+    #[test]
+    fn it_decodes_from_u8_slice() {
+        let message = DsoMessage::try_from(keepalive_request().as_slice()).unwrap();
+        assert_eq!(message.header.id, id::Id(42));
+        assert_eq!(message.primary, Some(tlv::Tlv::Keepalive { inactivity_timeout: 15_000, keepalive_interval: 50_000 }));
+        assert_eq!(message.additional, vec![tlv::Tlv::EncryptionPadding(3)]);
+    }
+
+    #[test]
+    fn it_decodes_response_without_tlvs() {
+        let mut buffer = keepalive_request();
+        buffer.truncate(12);
+        buffer[2] |= 0b1000_0000; // QR = Response
+        let message = DsoMessage::try_from(buffer.as_slice()).unwrap();
+        assert_eq!(message.primary, None);
+        assert!(message.additional.is_empty());
+    }
+
+    #[test]
+    fn it_reports_error_when_counts_nonzero() {
+        let mut buffer = keepalive_request();
+        buffer[9] = 0x01; // NSCOUNT = 1
+        assert_eq!(
+            DsoMessage::try_from(buffer.as_slice()),
+            Err(error::invalid_value(Field::NsUpCount, 8, 1)),
+        );
+    }
+
+    #[test]
+    fn it_encodes_to_u8_array() {
+        let message = DsoMessage::try_from(keepalive_request().as_slice()).unwrap();
+        let encoded: Vec<u8> = message.try_into().unwrap();
+        assert_eq!(encoded, keepalive_request());
+    }
+}
@@ -0,0 +1,140 @@
+use alloc::boxed::Box;
+use crate::name::Name;
+use crate::record::rdata::RData;
+use crate::record::{class, rtype, Record};
+
+/// TTL marking a change notification that removes a single record (RFC 8765 §6.3.1).
+pub const DELETE_RECORD_TTL: u32 = 0xFFFF_FFFF;
+/// TTL marking a change notification that removes RRsets (RFC 8765 §6.3.1).
+pub const DELETE_RRSETS_TTL: u32 = 0xFFFF_FFFE;
+/// Largest TTL an added record may carry.
+pub const MAX_ADD_TTL: u32 = 0x7FFF_FFFF;
+
+/// A change notification carried by a PUSH TLV (RFC 8765 §6.3.1).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    Add(Record),
+    DeleteRecord { name: Name, rtype: rtype::Type, class: class::Class, rdata: RData },
+    DeleteRrset { name: Name, rtype: rtype::Type, class: class::Class },
+    /// Removes every RRset of `name`, in all classes when `class` is ANY.
+    DeleteAll { name: Name, class: class::Class },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FromError {
+    InvalidChange(Box<Record>),
+}
+
+impl core::fmt::Display for FromError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidChange(record) => write!(f, "invalid change notification: {:?}", record),
+        }
+    }
+}
+
+impl core::error::Error for FromError {}
+
+impl Change {
+    pub fn try_from_record(record: Record) -> Result<Self, FromError> {
+        match (record.ttl, record.rtype, &record.rdata) {
+            (DELETE_RECORD_TTL, rtype::Type::Any, _) => Err(FromError::InvalidChange(Box::new(record))),
+            (DELETE_RECORD_TTL, rtype, _)            => Ok(Self::DeleteRecord {
+                name:  record.name,
+                rtype,
+                class: record.class,
+                rdata: record.rdata,
+            }),
+            (DELETE_RRSETS_TTL, rtype::Type::Any, RData::Empty) => Ok(Self::DeleteAll { name: record.name, class: record.class }),
+            (DELETE_RRSETS_TTL, rtype, RData::Empty)            => Ok(Self::DeleteRrset { name: record.name, rtype, class: record.class }),
+            (ttl, rtype, _) if ttl <= MAX_ADD_TTL && rtype != rtype::Type::Any && record.class != class::Class::Any => {
+                Ok(Self::Add(record))
+            },
+            _ => Err(FromError::InvalidChange(Box::new(record))),
+        }
+    }
+
+    pub fn to_record(&self) -> Record {
+        match self {
+            Self::Add(record)                                => record.clone(),
+            Self::DeleteRecord { name, rtype, class, rdata } => Record {
+                name:  name.clone(),
+                rtype: *rtype,
+                class: *class,
+                ttl:   DELETE_RECORD_TTL,
+                rdata: rdata.clone(),
+            },
+            Self::DeleteRrset { name, rtype, class }         => Record {
+                name:  name.clone(),
+                rtype: *rtype,
+                class: *class,
+                ttl:   DELETE_RRSETS_TTL,
+                rdata: RData::Empty,
+            },
+            Self::DeleteAll { name, class }                  => Record {
+                name:  name.clone(),
+                rtype: rtype::Type::Any,
+                class: *class,
+                ttl:   DELETE_RRSETS_TTL,
+                rdata: RData::Empty,
+            },
+        }
+    }
+
+    pub fn name(&self) -> &Name {
+        match self {
+            Self::Add(record)                                                 => &record.name,
+            Self::DeleteRecord { name, .. } | Self::DeleteRrset { name, .. } => name,
+            Self::DeleteAll { name, .. }                                      => name,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn a(ttl: u32) -> Record {
+        Record {
+            name:  "printer._ipp._tcp.local.".parse().unwrap(),
+            rtype: rtype::Type::A,
+            class: class::Class::In,
+            ttl,
+            rdata: RData::A(core::net::Ipv4Addr::new(192, 0, 2, 1)),
+        }
+    }
+
+    #[test]
+    fn it_converts_change_from_record() {
+        let name: Name = "printer._ipp._tcp.local.".parse().unwrap();
+        let cases = [
+            (a(120), Change::Add(a(120))),
+            (a(DELETE_RECORD_TTL), Change::DeleteRecord {
+                name:  name.clone(),
+                rtype: rtype::Type::A,
+                class: class::Class::In,
+                rdata: a(0).rdata,
+            }),
+            (Record { rdata: RData::Empty, ..a(DELETE_RRSETS_TTL) }, Change::DeleteRrset {
+                name:  name.clone(),
+                rtype: rtype::Type::A,
+                class: class::Class::In,
+            }),
+            (Record { rtype: rtype::Type::Any, class: class::Class::Any, rdata: RData::Empty, ..a(DELETE_RRSETS_TTL) }, Change::DeleteAll {
+                name:  name.clone(),
+                class: class::Class::Any,
+            }),
+        ];
+        for (record, change) in cases {
+            assert_eq!(Change::try_from_record(record.clone()), Ok(change.clone()));
+            assert_eq!(change.to_record(), record);
+        }
+    }
+
+    #[test]
+    fn it_does_not_convert_invalid_change() {
+        assert!(Change::try_from_record(a(0x8000_0000)).is_err(), "should not add with negative TTL");
+        assert!(Change::try_from_record(a(DELETE_RRSETS_TTL)).is_err(), "should not delete RRset with RDATA");
+    }
+}
@@ -0,0 +1,590 @@
+use alloc::{vec, vec::Vec};
+use crate::header::{id, one_bit_flag, opcode, rcode, Header};
+use super::tlv::{Tlv, Type};
+use super::DsoMessage;
+
+/// Timeouts in effect until a Keepalive TLV says otherwise (RFC 8490 §6.2).
+pub const DEFAULT_TIMEOUT: u32 = 15_000;
+/// Timer value that never expires (RFC 8490 §7.1).
+pub const INFINITE: u32 = u32::MAX;
+/// Smallest keepalive interval a client honours (RFC 8490 §6.5).
+pub const MIN_KEEPALIVE_INTERVAL: u32 = 10_000;
+/// Grace a server gives an inactive client, or a client told to go away, before aborting (RFC 8490 §6.4.1).
+pub const GRACE: u64 = 5_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Client,
+    Server,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    /// Plain DNS over a stream, no DSO request answered yet.
+    Connected,
+    /// The client sent its first DSO request and waits for the answer.
+    Establishing,
+    Established,
+    /// The server asked the client to go away and waits for it to close.
+    Closing,
+    Closed,
+}
+
+/// What the caller has to do on the connection. Times are milliseconds of the caller's clock.
+#[derive(Debug, Clone)]
+#[cfg_attr(test, derive(PartialEq, Eq))]
+pub enum Action {
+    Send(DsoMessage),
+    /// A message with a primary TLV the application registered with [`Session::support`].
+    Deliver(DsoMessage),
+    /// Close the connection gracefully, not reconnecting before `reconnect_after` when given.
+    Close { reconnect_after: Option<u64> },
+    /// Forcibly abort the connection after a protocol error (RFC 8490 §5.1.1).
+    Abort,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SessionError {
+    NotEstablished,
+    Closed,
+    /// Only the session itself sends TLVs of this type.
+    Reserved(Type),
+}
+
+impl core::fmt::Display for SessionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotEstablished     => write!(f, "DSO session is not established"),
+            Self::Closed             => write!(f, "DSO session is closed"),
+            Self::Reserved(dso_type) => write!(f, "DSO TLV {} is managed by the session", dso_type.to_hex_string()),
+        }
+    }
+}
+
+impl core::error::Error for SessionError {}
+
+/// Sans-IO state of one DSO session (RFC 8490), driven by decoded messages and the caller's clock.
+#[derive(Debug, Clone)]
+pub struct Session {
+    role:               Role,
+    state:              State,
+    inactivity_timeout: u32,
+    keepalive_interval: u32,
+    last_traffic:       u64,
+    closing_since:      u64,
+    next_id:            u16,
+    outstanding:        Vec<(id::Id, Type)>,
+    /// Message IDs of SUBSCRIBE requests answered with NOERROR and not yet unsubscribed,
+    /// long-lived operations that keep the session active (RFC 8490 §6.2, RFC 8765 §6.2).
+    subscriptions:      Vec<id::Id>,
+    supported:          Vec<Type>,
+}
+
+impl Session {
+    pub fn client(now: u64) -> Self {
+        Self::new(Role::Client, DEFAULT_TIMEOUT, DEFAULT_TIMEOUT, now)
+    }
+
+    /// A server session granting clients the given timers in its Keepalive responses.
+    pub fn server(inactivity_timeout: u32, keepalive_interval: u32, now: u64) -> Self {
+        Self::new(Role::Server, inactivity_timeout, keepalive_interval, now)
+    }
+
+    fn new(role: Role, inactivity_timeout: u32, keepalive_interval: u32, now: u64) -> Self {
+        Self {
+            role,
+            state:              State::Connected,
+            inactivity_timeout,
+            keepalive_interval,
+            last_traffic:       now,
+            closing_since:      now,
+            next_id:            1,
+            outstanding:        vec![],
+            subscriptions:      vec![],
+            supported:          vec![],
+        }
+    }
+
+    /// Lets messages with `dso_type` as primary TLV through to the application
+    /// instead of answering them with DSOTYPENI.
+    pub fn support(mut self, dso_type: Type) -> Self {
+        self.supported.push(dso_type);
+        self
+    }
+
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn inactivity_timeout(&self) -> u32 {
+        self.inactivity_timeout
+    }
+
+    pub fn keepalive_interval(&self) -> u32 {
+        self.keepalive_interval
+    }
+
+    /// Starts the session from the client side with a Keepalive request.
+    pub fn establish(&mut self, now: u64) -> Result<DsoMessage, SessionError> {
+        match (self.role, self.state) {
+            (Role::Client, State::Connected) => {},
+            (_, State::Closing | State::Closed) => return Err(SessionError::Closed),
+            _ => return Err(SessionError::NotEstablished),
+        }
+        self.state = State::Establishing;
+        Ok(self.keepalive_request(now))
+    }
+
+    /// Builds a request with an application TLV as primary and tracks its answer.
+    pub fn request(&mut self, now: u64, primary: Tlv, additional: Vec<Tlv>) -> Result<DsoMessage, SessionError> {
+        self.check_sendable(&primary)?;
+        let id = self.allocate_id();
+        self.outstanding.push((id, primary.dso_type()));
+        self.last_traffic = now;
+        Ok(DsoMessage {
+            header:  Header::query(id, opcode::Opcode::DnsStatefulOperations),
+            primary: Some(primary),
+            additional,
+        })
+    }
+
+    /// Builds a unidirectional message (message ID zero) carrying an application TLV.
+    pub fn unidirectional(&mut self, now: u64, primary: Tlv, additional: Vec<Tlv>) -> Result<DsoMessage, SessionError> {
+        self.check_sendable(&primary)?;
+        self.end_subscription(&primary);
+        self.last_traffic = now;
+        Ok(DsoMessage {
+            header:  Header::query(id::Id(0), opcode::Opcode::DnsStatefulOperations),
+            primary: Some(primary),
+            additional,
+        })
+    }
+
+    /// Answers a request delivered to the application. A server becomes established
+    /// by answering its first DSO request with NOERROR.
+    pub fn respond(&mut self, now: u64, request: &DsoMessage, rcode: rcode::Rcode, primary: Option<Tlv>) -> DsoMessage {
+        self.last_traffic = now;
+        if self.state == State::Connected && rcode == rcode::Rcode::NoError {
+            self.state = State::Established;
+        }
+        if matches!(request.primary, Some(Tlv::Subscribe(_))) && rcode == rcode::Rcode::NoError {
+            self.subscriptions.push(request.header.id);
+        }
+        response(request, rcode, primary)
+    }
+
+    /// Handles a message received from the peer.
+    pub fn receive(&mut self, now: u64, message: DsoMessage) -> Vec<Action> {
+        if self.state == State::Closed {
+            return vec![];
+        }
+        self.last_traffic = now;
+        match message.header.qr {
+            one_bit_flag::Qr::Response => self.receive_response(now, message),
+            one_bit_flag::Qr::Query    => self.receive_request(now, message),
+        }
+    }
+
+    fn receive_response(&mut self, now: u64, message: DsoMessage) -> Vec<Action> {
+        let position = match self.outstanding.iter().position(|(id, _)| *id == message.header.id) {
+            Some(position) => position,
+            None           => return self.abort(),
+        };
+        let (_, dso_type) = self.outstanding.remove(position);
+        if message.header.rcode == rcode::Rcode::NoError {
+            if self.state == State::Establishing {
+                self.state = State::Established;
+            }
+            if dso_type == Type::Subscribe {
+                self.subscriptions.push(message.header.id);
+            }
+        } else if self.state == State::Establishing {
+            self.state = State::Connected;
+        }
+        if let Some(delay) = message.additional.iter().find_map(|tlv| match tlv {
+            Tlv::RetryDelay(delay) => Some(*delay),
+            _ => None,
+        }) {
+            return self.close(Some(now + delay as u64));
+        }
+        match (dso_type, &message.primary) {
+            (Type::Keepalive, Some(Tlv::Keepalive { inactivity_timeout, keepalive_interval })) => {
+                self.adopt_timers(*inactivity_timeout, *keepalive_interval);
+                vec![]
+            },
+            (Type::Keepalive, _) => vec![],
+            _                    => vec![Action::Deliver(message)],
+        }
+    }
+
+    fn receive_request(&mut self, now: u64, message: DsoMessage) -> Vec<Action> {
+        let unidirectional = message.header.id == id::Id(0);
+        // only clients start sessions (RFC 8490 §5.1)
+        if self.role == Role::Client && self.state != State::Established {
+            return self.abort();
+        }
+        // nor may unidirectional messages come before one is established
+        if unidirectional && self.state == State::Connected {
+            return self.abort();
+        }
+        let dso_type = match &message.primary {
+            Some(primary) => primary.dso_type(),
+            None if unidirectional => return self.abort(),
+            None => return vec![Action::Send(response(&message, rcode::Rcode::FormatError, None))],
+        };
+        match (self.role, dso_type, unidirectional) {
+            (Role::Server, Type::Keepalive, false) => {
+                let primary = Tlv::Keepalive {
+                    inactivity_timeout: self.inactivity_timeout,
+                    keepalive_interval: self.keepalive_interval,
+                };
+                vec![Action::Send(self.respond(now, &message, rcode::Rcode::NoError, Some(primary)))]
+            },
+            (Role::Client, Type::Keepalive, true) => {
+                if let Some(Tlv::Keepalive { inactivity_timeout, keepalive_interval }) = message.primary {
+                    self.adopt_timers(inactivity_timeout, keepalive_interval);
+                }
+                vec![]
+            },
+            (Role::Client, Type::RetryDelay, true) => {
+                let delay = match message.primary {
+                    Some(Tlv::RetryDelay(delay)) => delay as u64,
+                    _ => 0,
+                };
+                self.close(Some(now + delay))
+            },
+            (_, Type::Keepalive | Type::RetryDelay | Type::EncryptionPadding, _) => self.abort(),
+            (_, dso_type, _) if self.supported.contains(&dso_type) => {
+                if let Some(primary) = &message.primary {
+                    self.end_subscription(primary);
+                }
+                vec![Action::Deliver(message)]
+            },
+            (_, _, true) => self.abort(),
+            (_, _, false) => vec![Action::Send(response(&message, rcode::Rcode::Dsotypeni, None))],
+        }
+    }
+
+    /// Fires expired timers. Call it whenever [`Session::next_timeout`] passed.
+    pub fn poll(&mut self, now: u64) -> Vec<Action> {
+        match self.state {
+            State::Established => {},
+            State::Closing if now >= self.closing_since + GRACE => return self.abort(),
+            _ => return vec![],
+        }
+        match self.role {
+            Role::Client => {
+                if self.is_idle() && expired(self.last_traffic, self.inactivity_timeout as u64, now) {
+                    return self.close(None);
+                }
+                if expired(self.last_traffic, self.keepalive_interval as u64, now) {
+                    return vec![Action::Send(self.keepalive_request(now))];
+                }
+                vec![]
+            },
+            Role::Server => {
+                if self.subscriptions.is_empty() && expired(self.last_traffic, self.server_inactivity_limit(), now) {
+                    self.state = State::Closing;
+                    self.closing_since = now;
+                    let retry_delay = self.unidirectional_message(Tlv::RetryDelay(0));
+                    return vec![Action::Send(retry_delay)];
+                }
+                vec![]
+            },
+        }
+    }
+
+    /// When [`Session::poll`] has to be called next, if ever.
+    pub fn next_timeout(&self) -> Option<u64> {
+        match (self.state, self.role) {
+            (State::Closing, _) => Some(self.closing_since + GRACE),
+            (State::Established, Role::Client) => {
+                let keepalive = deadline(self.last_traffic, self.keepalive_interval as u64);
+                let inactivity = deadline(self.last_traffic, self.inactivity_timeout as u64)
+                    .filter(|_| self.is_idle());
+                keepalive.into_iter().chain(inactivity).min()
+            },
+            (State::Established, Role::Server) => {
+                deadline(self.last_traffic, self.server_inactivity_limit()).filter(|_| self.subscriptions.is_empty())
+            },
+            _ => None,
+        }
+    }
+
+    /// Whether nothing keeps the session active: no request is outstanding and no subscription is live.
+    fn is_idle(&self) -> bool {
+        self.outstanding.is_empty() && self.subscriptions.is_empty()
+    }
+
+    fn end_subscription(&mut self, primary: &Tlv) {
+        if let Tlv::Unsubscribe(id) = primary {
+            self.subscriptions.retain(|subscription| subscription != id);
+        }
+    }
+
+    fn server_inactivity_limit(&self) -> u64 {
+        if self.inactivity_timeout == INFINITE {
+            return INFINITE as u64;
+        }
+        let timeout = self.inactivity_timeout as u64;
+        (timeout * 2).max(timeout + GRACE)
+    }
+
+    fn adopt_timers(&mut self, inactivity_timeout: u32, keepalive_interval: u32) {
+        self.inactivity_timeout = inactivity_timeout;
+        self.keepalive_interval = keepalive_interval.max(MIN_KEEPALIVE_INTERVAL);
+    }
+
+    fn check_sendable(&self, primary: &Tlv) -> Result<(), SessionError> {
+        match self.state {
+            State::Established => {},
+            State::Closing | State::Closed => return Err(SessionError::Closed),
+            _ => return Err(SessionError::NotEstablished),
+        }
+        match primary.dso_type() {
+            dso_type @ (Type::Keepalive | Type::RetryDelay | Type::EncryptionPadding) => Err(SessionError::Reserved(dso_type)),
+            _ => Ok(()),
+        }
+    }
+
+    fn keepalive_request(&mut self, now: u64) -> DsoMessage {
+        let id = self.allocate_id();
+        self.outstanding.push((id, Type::Keepalive));
+        self.last_traffic = now;
+        DsoMessage {
+            header:     Header::query(id, opcode::Opcode::DnsStatefulOperations),
+            primary:    Some(Tlv::Keepalive {
+                inactivity_timeout: self.inactivity_timeout,
+                keepalive_interval: self.keepalive_interval,
+            }),
+            additional: vec![],
+        }
+    }
+
+    fn unidirectional_message(&self, primary: Tlv) -> DsoMessage {
+        DsoMessage {
+            header:     Header::query(id::Id(0), opcode::Opcode::DnsStatefulOperations),
+            primary:    Some(primary),
+            additional: vec![],
+        }
+    }
+
+    fn allocate_id(&mut self) -> id::Id {
+        loop {
+            let id = id::Id(self.next_id);
+            self.next_id = self.next_id.wrapping_add(1).max(1);
+            // a subscription is named by the ID of its SUBSCRIBE until it ends (RFC 8765 §6.2)
+            if !self.outstanding.iter().any(|(outstanding, _)| *outstanding == id) && !self.subscriptions.contains(&id) {
+                return id;
+            }
+        }
+    }
+
+    fn close(&mut self, reconnect_after: Option<u64>) -> Vec<Action> {
+        self.state = State::Closed;
+        self.outstanding.clear();
+        self.subscriptions.clear();
+        vec![Action::Close { reconnect_after }]
+    }
+
+    fn abort(&mut self) -> Vec<Action> {
+        self.state = State::Closed;
+        self.outstanding.clear();
+        self.subscriptions.clear();
+        vec![Action::Abort]
+    }
+}
+
+fn response(request: &DsoMessage, rcode: rcode::Rcode, primary: Option<Tlv>) -> DsoMessage {
+    DsoMessage {
+        header:     Header { qr: one_bit_flag::Qr::Response, rcode, ..request.header },
+        primary,
+        additional: vec![],
+    }
+}
+
+fn deadline(since: u64, timeout: u64) -> Option<u64> {
+    (timeout != INFINITE as u64).then(|| since + timeout)
+}
+
+fn expired(since: u64, timeout: u64, now: u64) -> bool {
+    deadline(since, timeout).is_some_and(|deadline| now >= deadline)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn sent(actions: Vec<Action>) -> DsoMessage {
+        match actions.as_slice() {
+            [Action::Send(message)] => message.clone(),
+            _ => panic!("expected a single message to send, got {:?}", actions),
+        }
+    }
+
+    fn established(server_inactivity: u32, server_keepalive: u32) -> (Session, Session) {
+        let mut client = Session::client(0);
+        let mut server = Session::server(server_inactivity, server_keepalive, 0);
+        let request = client.establish(0).unwrap();
+        assert_eq!(client.state(), State::Establishing);
+        let response = sent(server.receive(10, request));
+        assert_eq!(server.state(), State::Established);
+        assert_eq!(client.receive(20, response), vec![]);
+        assert_eq!(client.state(), State::Established);
+        (client, server)
+    }
+
+    #[test]
+    fn it_establishes_session_with_server_timers() {
+        let (client, _) = established(60_000, 1_000);
+        assert_eq!(client.inactivity_timeout(), 60_000);
+        assert_eq!(client.keepalive_interval(), MIN_KEEPALIVE_INTERVAL);
+    }
+
+    #[test]
+    fn it_sends_keepalive_and_closes_when_inactive() {
+        let (mut client, _) = established(30_000, 20_000);
+        assert_eq!(client.next_timeout(), Some(20_020));
+        assert_eq!(client.poll(20_019), vec![]);
+        let keepalive = sent(client.poll(20_020));
+        assert_eq!(keepalive.primary.as_ref().map(Tlv::dso_type), Some(Type::Keepalive));
+        assert_eq!(client.next_timeout(), Some(40_020), "inactivity timer stops while a request is outstanding");
+
+        let response = response(&keepalive, rcode::Rcode::NoError, None);
+        assert_eq!(client.receive(20_030, response), vec![]);
+        assert_eq!(client.poll(50_030), vec![Action::Close { reconnect_after: None }]);
+        assert_eq!(client.state(), State::Closed);
+    }
+
+    #[test]
+    fn it_asks_inactive_client_to_go_away() {
+        let (_, mut server) = established(10_000, 20_000);
+        assert_eq!(server.next_timeout(), Some(20_010));
+        let retry_delay = sent(server.poll(20_010));
+        assert_eq!(retry_delay.header.id, id::Id(0));
+        assert_eq!(retry_delay.primary, Some(Tlv::RetryDelay(0)));
+        assert_eq!(server.state(), State::Closing);
+        assert_eq!(server.poll(25_010), vec![Action::Abort]);
+    }
+
+    #[test]
+    fn it_closes_on_retry_delay() {
+        let (mut client, server) = established(INFINITE, INFINITE);
+        assert_eq!(client.next_timeout(), None);
+        let message = server.unidirectional_message(Tlv::RetryDelay(3_000));
+        assert_eq!(client.receive(100, message), vec![Action::Close { reconnect_after: Some(3_100) }]);
+    }
+
+    #[test]
+    fn it_answers_unknown_primary_tlv_with_dsotypeni() {
+        let mut server = Session::server(DEFAULT_TIMEOUT, DEFAULT_TIMEOUT, 0);
+        let request = DsoMessage {
+            header:     Header::query(id::Id(5), opcode::Opcode::DnsStatefulOperations),
+            primary:    Some(Tlv::Unknown { dso_type: 0xF901, data: vec![] }),
+            additional: vec![],
+        };
+        let response = sent(server.receive(0, request.clone()));
+        assert_eq!(response.header.id, id::Id(5));
+        assert_eq!(response.header.qr, one_bit_flag::Qr::Response);
+        assert_eq!(response.header.rcode, rcode::Rcode::Dsotypeni);
+        assert_eq!(server.state(), State::Connected);
+
+        let mut server = Session::server(DEFAULT_TIMEOUT, DEFAULT_TIMEOUT, 0).support(Type::Unknown(0xF901));
+        assert_eq!(server.receive(0, request.clone()), vec![Action::Deliver(request.clone())]);
+        server.respond(0, &request, rcode::Rcode::NoError, None);
+        assert_eq!(server.state(), State::Established);
+    }
+
+    #[test]
+    fn it_aborts_on_protocol_errors() {
+        let (mut client, mut server) = established(DEFAULT_TIMEOUT, DEFAULT_TIMEOUT);
+        let unsubscribe = client.unidirectional(50, Tlv::Unsubscribe(id::Id(2)), vec![]).unwrap();
+        assert_eq!(server.receive(60, unsubscribe), vec![Action::Abort], "should abort on unsupported unidirectional TLV");
+
+        let (mut client, mut server) = established(DEFAULT_TIMEOUT, DEFAULT_TIMEOUT);
+        let unknown = server.unidirectional_message(Tlv::Unknown { dso_type: 0xF901, data: vec![] });
+        assert_eq!(client.receive(100, unknown), vec![Action::Abort]);
+
+        let retry_delay = server.unidirectional_message(Tlv::RetryDelay(0));
+        assert_eq!(server.receive(100, retry_delay), vec![Action::Abort]);
+
+        let mut client = Session::client(0);
+        let mut request = client.establish(0).unwrap();
+        request.header.id = id::Id(99);
+        let unsolicited = response(&request, rcode::Rcode::NoError, None);
+        assert_eq!(client.receive(10, unsolicited), vec![Action::Abort]);
+
+        let mut server = Session::server(DEFAULT_TIMEOUT, DEFAULT_TIMEOUT, 0).support(Type::Unsubscribe);
+        let early = server.unidirectional_message(Tlv::Unsubscribe(id::Id(2)));
+        assert_eq!(server.receive(10, early), vec![Action::Abort], "should abort on unidirectional message before establishment");
+    }
+
+    #[test]
+    fn it_tracks_application_requests() {
+        let (mut client, mut server) = established(DEFAULT_TIMEOUT, DEFAULT_TIMEOUT);
+        let tlv = Tlv::Unknown { dso_type: 0xF901, data: vec![1] };
+        assert_eq!(client.request(100, Tlv::RetryDelay(0), vec![]), Err(SessionError::Reserved(Type::RetryDelay)));
+        let request = client.request(100, tlv.clone(), vec![]).unwrap();
+        let response = sent(server.receive(110, request));
+        assert_eq!(response.header.rcode, rcode::Rcode::Dsotypeni);
+        assert_eq!(client.receive(120, response.clone()), vec![Action::Deliver(response)]);
+        assert_eq!(Session::client(0).request(0, tlv, vec![]), Err(SessionError::NotEstablished));
+    }
+
+    #[test]
+    fn it_delivers_push_subscriptions() {
+        let mut client = Session::client(0);
+        let mut server = Session::server(DEFAULT_TIMEOUT, DEFAULT_TIMEOUT, 0)
+            .support(Type::Subscribe)
+            .support(Type::Unsubscribe);
+        let response = sent(server.receive(10, client.establish(0).unwrap()));
+        client.receive(20, response);
+
+        let question = crate::question::Question {
+            name:   "_ipp._tcp.local.".parse().unwrap(),
+            qtype:  crate::record::rtype::Type::Ptr,
+            qclass: crate::record::class::Class::In,
+        };
+        let subscribe = client.request(30, Tlv::Subscribe(question), vec![]).unwrap();
+        assert_eq!(server.receive(40, subscribe.clone()), vec![Action::Deliver(subscribe)]);
+
+        let unsubscribe = client.unidirectional(50, Tlv::Unsubscribe(id::Id(2)), vec![]).unwrap();
+        assert_eq!(server.receive(60, unsubscribe.clone()), vec![Action::Deliver(unsubscribe)]);
+    }
+
+    #[test]
+    fn it_keeps_subscribed_session_active() {
+        let mut client = Session::client(0);
+        let mut server = Session::server(30_000, 20_000, 0)
+            .support(Type::Subscribe)
+            .support(Type::Unsubscribe);
+        let response = sent(server.receive(10, client.establish(0).unwrap()));
+        client.receive(20, response);
+
+        let question = crate::question::Question {
+            name:   "_ipp._tcp.local.".parse().unwrap(),
+            qtype:  crate::record::rtype::Type::Ptr,
+            qclass: crate::record::class::Class::In,
+        };
+        let subscribe = client.request(30, Tlv::Subscribe(question), vec![]).unwrap();
+        assert_eq!(server.receive(40, subscribe.clone()), vec![Action::Deliver(subscribe.clone())]);
+        let response = server.respond(50, &subscribe, rcode::Rcode::NoError, None);
+        assert_eq!(client.receive(60, response.clone()), vec![Action::Deliver(response)]);
+
+        assert_eq!(client.next_timeout(), Some(20_060), "inactivity timer stops while subscribed");
+        let keepalive = sent(client.poll(30_060));
+        assert_eq!(keepalive.primary.as_ref().map(Tlv::dso_type), Some(Type::Keepalive));
+        assert_eq!(server.next_timeout(), None);
+        assert_eq!(server.poll(100_000), vec![]);
+        assert_eq!(server.state(), State::Established);
+
+        let unsubscribe = client.unidirectional(100_010, Tlv::Unsubscribe(subscribe.header.id), vec![]).unwrap();
+        assert_eq!(server.receive(100_020, unsubscribe.clone()), vec![Action::Deliver(unsubscribe)]);
+        assert_eq!(server.next_timeout(), Some(160_020));
+    }
+}
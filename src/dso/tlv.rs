@@ -0,0 +1,311 @@
+use alloc::{format, string::String, vec, vec::Vec};
+use crate::reader::Reader;
+use crate::error::{self, DecodeError, EncodeError, Field};
+use crate::header::id;
+use crate::name::Name;
+use crate::question::Question;
+use crate::record::rdata::RData;
+use crate::record::{class, rtype, Record};
+use crate::sink::Sink;
+use super::push;
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Type {
+    Keepalive,
+    RetryDelay,
+    EncryptionPadding,
+    Subscribe,
+    Push,
+    Unsubscribe,
+    Reconfirm,
+    Unknown(u16),
+}
+
+impl Type {
+    pub fn to_hex_string(&self) -> String {
+        let value: u16 = (*self).into();
+        format!("{:04X}", value)
+    }
+}
+
+impl core::convert::From<u16> for Type {
+    fn from(value: u16) -> Self {
+        match value {
+            1    => Self::Keepalive        ,
+            2    => Self::RetryDelay       ,
+            3    => Self::EncryptionPadding,
+            0x40 => Self::Subscribe        ,
+            0x41 => Self::Push             ,
+            0x42 => Self::Unsubscribe      ,
+            0x43 => Self::Reconfirm        ,
+            v    => Self::Unknown(v),
+        }
+    }
+}
+
+impl core::convert::From<Type> for u16 {
+    fn from(value: Type) -> Self {
+        match value {
+            Type::Keepalive         => 1,
+            Type::RetryDelay        => 2,
+            Type::EncryptionPadding => 3,
+            Type::Subscribe         => 0x40,
+            Type::Push              => 0x41,
+            Type::Unsubscribe       => 0x42,
+            Type::Reconfirm         => 0x43,
+            Type::Unknown(v)        => v,
+        }
+    }
+}
+
+/// A DSO TLV (RFC 8490 §5.4). Timeouts and delays are in milliseconds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Tlv {
+    Keepalive { inactivity_timeout: u32, keepalive_interval: u32 },
+    RetryDelay(u32),
+    /// Number of padding octets (RFC 8490 §7.3).
+    EncryptionPadding(u16),
+    /// Asks for change notifications of an RRset (RFC 8765 §6.2).
+    Subscribe(Question),
+    /// Change notifications, at least one (RFC 8765 §6.3).
+    Push(Vec<push::Change>),
+    /// Cancels the subscription made by the SUBSCRIBE request with this message ID (RFC 8765 §6.4).
+    Unsubscribe(id::Id),
+    /// Asks the server to verify that a record still exists (RFC 8765 §6.5).
+    Reconfirm { name: Name, rtype: rtype::Type, class: class::Class, rdata: RData },
+    Unknown { dso_type: u16, data: Vec<u8> },
+}
+
+impl Tlv {
+    pub fn dso_type(&self) -> Type {
+        match self {
+            Self::Keepalive { .. }         => Type::Keepalive,
+            Self::RetryDelay(_)            => Type::RetryDelay,
+            Self::EncryptionPadding(_)     => Type::EncryptionPadding,
+            Self::Subscribe(_)             => Type::Subscribe,
+            Self::Push(_)                  => Type::Push,
+            Self::Unsubscribe(_)           => Type::Unsubscribe,
+            Self::Reconfirm { .. }         => Type::Reconfirm,
+            Self::Unknown { dso_type, .. } => Type::from(*dso_type),
+        }
+    }
+
+    pub(crate) fn read_from(buffer: &mut Reader) -> Result<Self, DecodeError> {
+        let dso_type = buffer.read_u16_field(Field::DsoType)?;
+        let length_at = buffer.position();
+        let length = buffer.read_u16_field(Field::DsoLength)?;
+        let data_at = buffer.position();
+        let data = buffer.read_bytes_field(length as usize, Field::DsoData(Type::from(dso_type)))?;
+        let tlv = Self::read_data(dso_type, &data).map_err(|e| e.shifted(data_at))?;
+        match tlv {
+            Some(tlv) => Ok(tlv),
+            None      => Err(error::length_mismatch(Field::DsoLength, length_at, length as usize)),
+        }
+    }
+
+    /// Reads the TLV from its DSO-DATA, or `None` when DSO-LENGTH does not fit the TLV.
+    fn read_data(dso_type: u16, data: &[u8]) -> Result<Option<Self>, DecodeError> {
+        let field = Field::DsoData(Type::from(dso_type));
+        let mut buffer = Reader::new(data);
+        let tlv = match Type::from(dso_type) {
+            Type::Keepalive         => Self::Keepalive {
+                inactivity_timeout: buffer.read_u32_field(field)?,
+                keepalive_interval: buffer.read_u32_field(field)?,
+            },
+            Type::RetryDelay        => Self::RetryDelay(buffer.read_u32_field(field)?),
+            Type::EncryptionPadding => {
+                buffer.set_position(data.len());
+                Self::EncryptionPadding(data.len() as u16)
+            },
+            Type::Subscribe         => Self::Subscribe(Question::read_from(&mut buffer)?),
+            Type::Push              => {
+                let mut changes = vec![];
+                while buffer.position() < data.len() {
+                    let offset = buffer.position();
+                    let record = Record::read_from(&mut buffer)?;
+                    let ttl = record.ttl;
+                    let change = push::Change::try_from_record(record)
+                        .map_err(|_| error::invalid_value(field, offset, ttl))?;
+                    changes.push(change);
+                }
+                if changes.is_empty() {
+                    return Ok(None);
+                }
+                Self::Push(changes)
+            },
+            Type::Unsubscribe       => Self::Unsubscribe(id::Id(buffer.read_u16_field(field)?)),
+            Type::Reconfirm         => {
+                let name = Name::read_from(&mut buffer)?;
+                let rtype = rtype::Type::from(buffer.read_u16_field(Field::Type)?);
+                let class = class::Class::from(buffer.read_u16_field(Field::Class)?);
+                let length = data.len().saturating_sub(buffer.position()) as u16;
+                let rdata = RData::read_from(rtype, length, &mut buffer)?;
+                Self::Reconfirm { name, rtype, class, rdata }
+            },
+            Type::Unknown(_)        => {
+                buffer.set_position(data.len());
+                Self::Unknown { dso_type, data: data.to_vec() }
+            },
+        };
+        if buffer.position() != data.len() {
+            return Ok(None);
+        }
+        Ok(Some(tlv))
+    }
+
+    pub(crate) fn write_to(&self, buffer: &mut impl Sink) -> Result<(), EncodeError> {
+        buffer.put_slice(&u16::from(self.dso_type()).to_be_bytes());
+        // DSO-LENGTH is filled in once DSO-DATA has been written
+        let length_at = buffer.written();
+        buffer.put_slice(&[0, 0]);
+        match self {
+            Self::Keepalive { inactivity_timeout, keepalive_interval } => {
+                buffer.put_slice(&inactivity_timeout.to_be_bytes());
+                buffer.put_slice(&keepalive_interval.to_be_bytes());
+            },
+            Self::RetryDelay(delay)         => buffer.put_slice(&delay.to_be_bytes()),
+            Self::EncryptionPadding(length) => {
+                for _ in 0..*length {
+                    buffer.put_u8(0);
+                }
+            },
+            Self::Subscribe(question)       => question.write_to(buffer),
+            Self::Push(changes)             => {
+                for change in changes {
+                    change.to_record().write_to(buffer)?;
+                }
+            },
+            Self::Unsubscribe(id)           => buffer.put_slice(&id.0.to_be_bytes()),
+            Self::Reconfirm { name, rtype, class, rdata } => {
+                name.write_to(buffer);
+                buffer.put_slice(&u16::from(*rtype).to_be_bytes());
+                buffer.put_slice(&u16::from(*class).to_be_bytes());
+                rdata.write_to(buffer)?;
+            },
+            Self::Unknown { data, .. }      => buffer.put_slice(data),
+        }
+        let length = buffer.written() - length_at - 2;
+        let length = u16::try_from(length)
+            .map_err(|_| error::too_long(Field::DsoLength, length_at, length))?;
+        buffer.patch(length_at, &length.to_be_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_converts_type_from_u16() {
+        assert_eq!(Type::from(1_u16), Type::Keepalive        );
+        assert_eq!(Type::from(2_u16), Type::RetryDelay       );
+        assert_eq!(Type::from(3_u16), Type::EncryptionPadding);
+        assert_eq!(Type::from(0x40_u16), Type::Subscribe        );
+        assert_eq!(Type::from(0x41_u16), Type::Push             );
+        assert_eq!(Type::from(0x42_u16), Type::Unsubscribe      );
+        assert_eq!(Type::from(0x43_u16), Type::Reconfirm        );
+        assert_eq!(Type::from(0xF800_u16), Type::Unknown(0xF800));
+        assert_eq!(Type::Keepalive.to_hex_string(), "0001");
+    }
+
+    #[test]
+    fn it_reads_and_writes_tlvs() {
+        let buffer: &[u8] = &[
+            0x00, 0x01, 0x00, 0x08,  // DSO-TYPE = Keepalive, DSO-LENGTH = 8
+            0x00, 0x00, 0x3A, 0x98,  // INACTIVITY TIMEOUT = 15000
+            0x00, 0x00, 0xC3, 0x50,  // KEEPALIVE INTERVAL = 50000
+            0x00, 0x02, 0x00, 0x04,  // DSO-TYPE = Retry Delay, DSO-LENGTH = 4
+            0x00, 0x00, 0x03, 0xE8,  // RETRY DELAY = 1000
+            0x00, 0x03, 0x00, 0x02,  // DSO-TYPE = Encryption Padding, DSO-LENGTH = 2
+            0x00, 0x00,
+            0xF8, 0x00, 0x00, 0x01,  // DSO-TYPE = experimental, DSO-LENGTH = 1
+            0xAB,
+        ];
+        let mut cursor = Reader::new(buffer);
+        let mut tlvs = vec![];
+        while (cursor.position()) < buffer.len() {
+            tlvs.push(Tlv::read_from(&mut cursor).unwrap());
+        }
+        assert_eq!(tlvs, vec![
+            Tlv::Keepalive { inactivity_timeout: 15_000, keepalive_interval: 50_000 },
+            Tlv::RetryDelay(1_000),
+            Tlv::EncryptionPadding(2),
+            Tlv::Unknown { dso_type: 0xF800, data: vec![0xAB] },
+        ]);
+        let mut encoded = vec![];
+        for tlv in &tlvs {
+            tlv.write_to(&mut encoded).unwrap();
+        }
+        assert_eq!(encoded, buffer);
+    }
+
+    #[test]
+    fn it_reports_error_when_length_mismatch() {
+        let buffer: &[u8] = &[0x00, 0x02, 0x00, 0x05, 0x00, 0x00, 0x03, 0xE8, 0x00];
+        assert_eq!(Tlv::read_from(&mut Reader::new(buffer)), Err(error::length_mismatch(Field::DsoLength, 2, 5)));
+
+        let buffer: &[u8] = &[0x00, 0x01, 0x00, 0x08, 0x00, 0x00];
+        assert_eq!(Tlv::read_from(&mut Reader::new(buffer)), Err(error::truncated(Field::DsoData(Type::Keepalive), 4)));
+    }
+
+    #[test]
+    fn it_reads_and_writes_push_tlvs() {
+        let buffer: &[u8] = &[
+            0x00, 0x40, 0x00, 0x09,              // DSO-TYPE = SUBSCRIBE, DSO-LENGTH = 9
+            0x03, 0x77, 0x77, 0x77, 0x00,        // NAME = www.
+            0x00, 0x1C,                          // TYPE = AAAA
+            0x00, 0x01,                          // CLASS = IN
+            0x00, 0x42, 0x00, 0x02,              // DSO-TYPE = UNSUBSCRIBE, DSO-LENGTH = 2
+            0x12, 0x34,                          // SUBSCRIBE MESSAGE ID = 0x1234
+            0x00, 0x41, 0x00, 0x22,              // DSO-TYPE = PUSH, DSO-LENGTH = 34
+            0x03, 0x77, 0x77, 0x77, 0x00,        // NAME = www.
+            0x00, 0x01, 0x00, 0x01,              // TYPE = A, CLASS = IN
+            0x00, 0x00, 0x00, 0x3C,              // TTL = 60
+            0x00, 0x04, 0xC0, 0x00, 0x02, 0x01,  // RDATA = 192.0.2.1
+            0x03, 0x77, 0x77, 0x77, 0x00,        // NAME = www.
+            0x00, 0x1C, 0x00, 0x01,              // TYPE = AAAA, CLASS = IN
+            0xFF, 0xFF, 0xFF, 0xFE,              // TTL = delete RRset
+            0x00, 0x00,                          // RDLENGTH = 0
+            0x00, 0x43, 0x00, 0x0D,              // DSO-TYPE = RECONFIRM, DSO-LENGTH = 13
+            0x03, 0x77, 0x77, 0x77, 0x00,        // NAME = www.
+            0x00, 0x01, 0x00, 0x01,              // TYPE = A, CLASS = IN
+            0xC0, 0x00, 0x02, 0x01,              // RDATA = 192.0.2.1
+        ];
+        let name: Name = "www.".parse().unwrap();
+        let address = RData::A(core::net::Ipv4Addr::new(192, 0, 2, 1));
+        let mut cursor = Reader::new(buffer);
+        let mut tlvs = vec![];
+        while (cursor.position()) < buffer.len() {
+            tlvs.push(Tlv::read_from(&mut cursor).unwrap());
+        }
+        assert_eq!(tlvs, vec![
+            Tlv::Subscribe(Question { name: name.clone(), qtype: rtype::Type::Aaaa, qclass: class::Class::In }),
+            Tlv::Unsubscribe(id::Id(0x1234)),
+            Tlv::Push(vec![
+                push::Change::Add(Record {
+                    name:  name.clone(),
+                    rtype: rtype::Type::A,
+                    class: class::Class::In,
+                    ttl:   60,
+                    rdata: address.clone(),
+                }),
+                push::Change::DeleteRrset { name: name.clone(), rtype: rtype::Type::Aaaa, class: class::Class::In },
+            ]),
+            Tlv::Reconfirm { name, rtype: rtype::Type::A, class: class::Class::In, rdata: address },
+        ]);
+        let mut encoded = vec![];
+        for tlv in &tlvs {
+            tlv.write_to(&mut encoded).unwrap();
+        }
+        assert_eq!(encoded, buffer);
+    }
+
+    #[test]
+    fn it_reports_error_when_push_is_empty() {
+        let buffer: &[u8] = &[0x00, 0x41, 0x00, 0x00];
+        assert_eq!(Tlv::read_from(&mut Reader::new(buffer)), Err(error::length_mismatch(Field::DsoLength, 2, 0)));
+    }
+}
@@ -0,0 +1,304 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use crate::dso::tlv;
+#[cfg(feature = "alloc")]
+use crate::record::rtype;
+#[cfg(feature = "alloc")]
+use crate::update;
+
+/// Part of a message an error happened in.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Header,
+    /// Question section, or zone section of an UPDATE.
+    Question,
+    /// Answer section, or prerequisite section of an UPDATE.
+    Answer,
+    /// Authority section, or update section of an UPDATE.
+    Authority,
+    Additional,
+    /// TLVs following the header of a DSO message.
+    Dso,
+}
+
+/// Wire field an error happened in.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Id,
+    Qr,
+    Opcode,
+    Aa,
+    Tc,
+    Rd,
+    Ra,
+    Z,
+    Ad,
+    Cd,
+    Rcode,
+    QdZoCount,
+    AnPrCount,
+    NsUpCount,
+    Arcount,
+    Label,
+    Pointer,
+    Name,
+    Type,
+    Class,
+    Ttl,
+    RdLength,
+    #[cfg(feature = "alloc")]
+    RData(rtype::Type),
+    DsoType,
+    DsoLength,
+    #[cfg(feature = "alloc")]
+    DsoData(tlv::Type),
+    /// Two-octet length prefixed to messages on TCP (RFC 1035 §4.2.2).
+    TcpLength,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The buffer ended inside `field`.
+    Truncated { section: Section, field: Field, offset: usize },
+    /// `field` holds a value that is not allowed there.
+    InvalidValue { section: Section, field: Field, offset: usize, value: u32 },
+    /// The length given for `field` disagrees with its content.
+    LengthMismatch { section: Section, field: Field, offset: usize, length: usize },
+    /// Bytes left over after the last section.
+    TrailingBytes { section: Section, offset: usize },
+    /// Records that do not form a valid UPDATE (RFC 2136 §2.4, §2.5).
+    #[cfg(feature = "alloc")]
+    InvalidUpdate { section: Section, cause: update::FromError },
+}
+
+impl DecodeError {
+    pub fn section(&self) -> Section {
+        match self {
+            Self::Truncated { section, .. } | Self::InvalidValue { section, .. }      => *section,
+            Self::LengthMismatch { section, .. } | Self::TrailingBytes { section, .. } => *section,
+            #[cfg(feature = "alloc")]
+            Self::InvalidUpdate { section, .. }                                       => *section,
+        }
+    }
+
+    pub fn field(&self) -> Option<Field> {
+        match self {
+            Self::Truncated { field, .. } | Self::InvalidValue { field, .. } => Some(*field),
+            Self::LengthMismatch { field, .. }                               => Some(*field),
+            Self::TrailingBytes { .. }                                       => None,
+            #[cfg(feature = "alloc")]
+            Self::InvalidUpdate { .. }                                       => None,
+        }
+    }
+
+    /// Byte offset from the start of the buffer being decoded.
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            Self::Truncated { offset, .. } | Self::InvalidValue { offset, .. } => Some(*offset),
+            Self::LengthMismatch { offset, .. } | Self::TrailingBytes { offset, .. } => Some(*offset),
+            #[cfg(feature = "alloc")]
+            Self::InvalidUpdate { .. } => None,
+        }
+    }
+
+    /// Attributes the error to `section`, which nested readers do not know about.
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    pub(crate) fn in_section(self, section: Section) -> Self {
+        match self {
+            Self::Truncated { field, offset, .. }              => Self::Truncated { section, field, offset },
+            Self::InvalidValue { field, offset, value, .. }    => Self::InvalidValue { section, field, offset, value },
+            Self::LengthMismatch { field, offset, length, .. } => Self::LengthMismatch { section, field, offset, length },
+            Self::TrailingBytes { offset, .. }                 => Self::TrailingBytes { section, offset },
+            #[cfg(feature = "alloc")]
+            Self::InvalidUpdate { cause, .. }                  => Self::InvalidUpdate { section, cause },
+        }
+    }
+
+    /// Shifts the offset of an error raised while reading a slice that starts at `by`.
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    pub(crate) fn shifted(self, by: usize) -> Self {
+        match self {
+            Self::Truncated { section, field, offset }              => Self::Truncated { section, field, offset: offset + by },
+            Self::InvalidValue { section, field, offset, value }    => Self::InvalidValue { section, field, offset: offset + by, value },
+            Self::LengthMismatch { section, field, offset, length } => Self::LengthMismatch { section, field, offset: offset + by, length },
+            Self::TrailingBytes { section, offset }                 => Self::TrailingBytes { section, offset: offset + by },
+            #[cfg(feature = "alloc")]
+            error @ Self::InvalidUpdate { .. }                      => error,
+        }
+    }
+}
+
+impl core::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Truncated { section, field, offset } => {
+                write!(f, "{:?} section: message ends inside {:?} at {} byte", section, field, offset)
+            },
+            Self::InvalidValue { section, field, offset, value } => {
+                write!(f, "{:?} section: invalid {:?} {:#X} at {} byte", section, field, value, offset)
+            },
+            Self::LengthMismatch { section, field, offset, length } => {
+                write!(f, "{:?} section: length {} of {:?} at {} byte does not match its content", section, length, field, offset)
+            },
+            Self::TrailingBytes { section, offset } => write!(f, "{:?} section: found trailing bytes from {} byte", section, offset),
+            #[cfg(feature = "alloc")]
+            Self::InvalidUpdate { section, cause } => write!(f, "{:?} section: {}", section, cause),
+        }
+    }
+}
+
+impl core::error::Error for DecodeError {}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodeError {
+    /// `field` does not fit the width it has on the wire.
+    ValueTooLarge { section: Section, field: Field, offset: usize, value: u64 },
+    /// The content of `field` is longer than its length field can express.
+    TooLong { section: Section, field: Field, offset: usize, length: usize },
+    /// The caller-provided buffer lacks `needed` more bytes.
+    BufferTooSmall { needed: usize },
+}
+
+impl EncodeError {
+    pub fn section(&self) -> Option<Section> {
+        match self {
+            Self::ValueTooLarge { section, .. } | Self::TooLong { section, .. } => Some(*section),
+            Self::BufferTooSmall { .. }                                         => None,
+        }
+    }
+
+    pub fn field(&self) -> Option<Field> {
+        match self {
+            Self::ValueTooLarge { field, .. } | Self::TooLong { field, .. } => Some(*field),
+            Self::BufferTooSmall { .. }                                     => None,
+        }
+    }
+
+    /// Byte offset in the output where the field starts.
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            Self::ValueTooLarge { offset, .. } | Self::TooLong { offset, .. } => Some(*offset),
+            Self::BufferTooSmall { .. }                                       => None,
+        }
+    }
+
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    pub(crate) fn in_section(self, section: Section) -> Self {
+        match self {
+            Self::ValueTooLarge { field, offset, value, .. } => Self::ValueTooLarge { section, field, offset, value },
+            Self::TooLong { field, offset, length, .. }      => Self::TooLong { section, field, offset, length },
+            error @ Self::BufferTooSmall { .. }              => error,
+        }
+    }
+}
+
+impl core::fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::ValueTooLarge { section, field, offset, value } => {
+                write!(f, "{:?} section: {:?} {:#X} at {} byte does not fit the wire format", section, field, value, offset)
+            },
+            Self::TooLong { section, field, offset, length } => {
+                write!(f, "{:?} section: {:?} at {} byte is too long: {} octets", section, field, offset, length)
+            },
+            Self::BufferTooSmall { needed } => write!(f, "buffer is too small: {} more bytes needed", needed),
+        }
+    }
+}
+
+impl core::error::Error for EncodeError {}
+
+/// Failure to sign a message with TSIG or SIG(0).
+#[cfg(feature = "alloc")]
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum SignError {
+    /// The message to be signed could not be encoded.
+    Encode(EncodeError),
+    /// The `Signer` supplied by the application failed.
+    Signer(Box<dyn core::error::Error>),
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Display for SignError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Encode(cause) => write!(f, "cannot encode message to sign: {}", cause),
+            Self::Signer(cause) => write!(f, "signer failed: {}", cause),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for SignError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Encode(cause) => Some(cause),
+            Self::Signer(cause) => Some(cause.as_ref()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<EncodeError> for SignError {
+    fn from(error: EncodeError) -> Self {
+        Self::Encode(error)
+    }
+}
+
+/// A string that names no value of `kind`, such as an unknown OPCODE mnemonic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownMnemonic {
+    pub kind: &'static str,
+}
+
+impl core::fmt::Display for UnknownMnemonic {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "unknown {} mnemonic", self.kind)
+    }
+}
+
+impl core::error::Error for UnknownMnemonic {}
+
+/// Nested readers report [`Section::Header`] until the caller attributes the error with `in_section`.
+pub(crate) fn truncated(field: Field, offset: usize) -> DecodeError {
+    DecodeError::Truncated { section: Section::Header, field, offset }
+}
+
+pub(crate) fn invalid_value(field: Field, offset: usize, value: u32) -> DecodeError {
+    DecodeError::InvalidValue { section: Section::Header, field, offset, value }
+}
+
+#[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+pub(crate) fn length_mismatch(field: Field, offset: usize, length: usize) -> DecodeError {
+    DecodeError::LengthMismatch { section: Section::Header, field, offset, length }
+}
+
+pub(crate) fn value_too_large(field: Field, offset: usize, value: u64) -> EncodeError {
+    EncodeError::ValueTooLarge { section: Section::Header, field, offset, value }
+}
+
+#[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+pub(crate) fn too_long(field: Field, offset: usize, length: usize) -> EncodeError {
+    EncodeError::TooLong { section: Section::Header, field, offset, length }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_attributes_error_to_section() {
+        let error = invalid_value(Field::Label, 12, 0x40).in_section(Section::Answer);
+        assert_eq!(error.section(), Section::Answer);
+        assert_eq!(error.field(), Some(Field::Label));
+        assert_eq!(error.offset(), Some(12));
+        assert_eq!(error.to_string(), "Answer section: invalid Label 0x40 at 12 byte");
+    }
+}
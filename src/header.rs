@@ -1,5 +1,6 @@
-use std::io::Cursor;
-use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use crate::reader::Reader;
+use crate::error::{self, DecodeError, EncodeError, Field};
+use crate::sink::{self, Sink};
 
 pub mod one_bit_flag;
 pub mod id;
@@ -7,6 +8,8 @@ pub mod opcode;
 pub mod z;
 pub mod rcode;
 pub mod count;
+pub mod builder;
+pub mod raw;
 
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(test, derive(PartialEq, Eq))]
@@ -28,69 +31,28 @@ pub struct Header {
     pub arcount:     count::Arcount,
 }
 
-#[derive(Debug)]
-pub struct InvalidValueError<InvalidValue>
-    where InvalidValue: std::fmt::Display
-{
-    name: String,
-    invalid_value: InvalidValue,
-    additional_info: Option<String>,
-}
-
-impl<InvalidValue> std::fmt::Display for InvalidValueError<InvalidValue>
-    where InvalidValue: std::fmt::Display
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.additional_info.is_none() {
-            write!(f, "found invalid {} value: {}", self.name, self.invalid_value)
-        } else {
-            write!(f, "found invalid {} value ({}): {}", self.name, self.additional_info.clone().unwrap(), self.invalid_value)
-        }
-    }
-}
-
-impl<InvalidValue> std::error::Error for InvalidValueError<InvalidValue>
-    where InvalidValue: std::fmt::Debug + std::fmt::Display {}
+pub(crate) const FLAGS_OFFSET: usize = 2;
+const HEADER_LENGTH: usize = 12;
 
-#[derive(Debug)]
-pub struct HeaderReadError {
-    cause: String,
-    range: (u8, u8),
-}
+impl core::convert::TryFrom<&[u8]> for Header {
+    type Error = DecodeError;
 
-impl std::fmt::Display for HeaderReadError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "cannot read header from {} to {} bit", self.range.0, self.range.1)?;
-        write!(f, "{}", self.cause)
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Self::read(value, false)
     }
 }
 
-impl std::error::Error for HeaderReadError {}
-
-#[derive(Debug)]
-pub struct HeaderWriteError {
-    cause: String,
-    property_name: String,
-}
-
-impl std::fmt::Display for HeaderWriteError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "cannot write header property: {}", self.property_name)?;
-        write!(f, "{}", self.cause)
+impl Header {
+    /// Decodes like `try_from`, but keeps a nonzero Z bit and unassigned OPCODE and RCODE values
+    /// as `Unknown` variants instead of rejecting them, so that the header re-encodes to the same bytes.
+    pub fn try_from_lenient(value: &[u8]) -> Result<Self, DecodeError> {
+        Self::read(value, true)
     }
-}
 
-impl std::error::Error for HeaderWriteError {}
-
-impl std::convert::TryFrom<&[u8]> for Header {
-    type Error = Box<dyn std::error::Error>;
-
-    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
-        let mut buffer = Cursor::new(value);
-        let id = buffer.read_u16::<BigEndian>()
-            .map_err(|e| HeaderReadError { cause: e.to_string(), range: (1, 16) })?;
-        let chunk = buffer.read_u16::<BigEndian>()
-            .map_err(|e| HeaderReadError { cause: e.to_string(), range: (17, 32) })?;
+    fn read(value: &[u8], lenient: bool) -> Result<Self, DecodeError> {
+        let mut buffer = Reader::new(value);
+        let id = buffer.read_u16_field(Field::Id)?;
+        let chunk = buffer.read_u16_field(Field::Qr)?;
         let qr     = ((chunk & 0b1000_0000_0000_0000_u16) >> 15) as u8;
         let opcode = ((chunk & 0b0111_1000_0000_0000_u16) >> 11) as u8;
         let aa     = ((chunk & 0b0000_0100_0000_0000_u16) >> 10) as u8;
@@ -101,64 +63,41 @@ impl std::convert::TryFrom<&[u8]> for Header {
         let ad     = ((chunk & 0b0000_0000_0010_0000_u16) >>  5) as u8;
         let cd     = ((chunk & 0b0000_0000_0001_0000_u16) >>  4) as u8;
         let rcode     =  chunk & 0b0000_0000_0000_1111_u16;
-        let qd_zo_count = buffer.read_u16::<BigEndian>()
-            .map(count::QdZoCount)
-            .map_err(|e| HeaderReadError { cause: e.to_string(), range: (33, 48) })?;
-        let an_pr_count  = buffer.read_u16::<BigEndian>()
-            .map(count::AnPrCount)
-            .map_err(|e| HeaderReadError { cause: e.to_string(), range: (49, 64) })?;
-        let ns_up_count = buffer.read_u16::<BigEndian>()
-            .map(count::NsUpCount)
-            .map_err(|e| HeaderReadError { cause: e.to_string(), range: (65, 80) })?;
-        let arcount = buffer.read_u16::<BigEndian>()
-            .map(count::Arcount)
-            .map_err(|e| HeaderReadError { cause: e.to_string(), range: (81, 96) })?;
-
-        let qr = one_bit_flag::Qr::try_from(qr)
-            .map_err(|invalid_value| InvalidValueError { name: "QR".to_string(), invalid_value, additional_info: None })?;
-        let opcode = opcode::Opcode::try_from(opcode)
-            .map_err(
-                |invalid_value| {
-                    let cause = match invalid_value {
-                        opcode::FromError::InvalidRange(invalid_value) => (invalid_value, "range invalid"     ),
-                        opcode::FromError::Unassigned(invalid_value)   => (invalid_value, "unassigned in RFC"),
-                    };
-                    InvalidValueError {
-                        name: "OPCODE".to_string(),
-                        invalid_value: cause.0,
-                        additional_info: Some(cause.1.to_string()),
-                    }
-                }
-            )?;
-        let aa = one_bit_flag::Aa::try_from(aa)
-            .map_err(|invalid_value| InvalidValueError { name: "AA".to_string(), invalid_value, additional_info: None })?;
-        let tc = one_bit_flag::Tc::try_from(tc)
-            .map_err(|invalid_value| InvalidValueError { name: "TC".to_string(), invalid_value, additional_info: None })?;
-        let rd = one_bit_flag::Rd::try_from(rd)
-            .map_err(|invalid_value| InvalidValueError { name: "RD".to_string(), invalid_value, additional_info: None })?;
-        let ra = one_bit_flag::Ra::try_from(ra)
-            .map_err(|invalid_value| InvalidValueError { name: "RA".to_string(), invalid_value, additional_info: None })?;
-        if z != 0_u8 {
-            Err(InvalidValueError { name: "Z".to_string(), invalid_value: z, additional_info: None })?;
-        }
-        let ad = one_bit_flag::Ad::try_from(ad)
-            .map_err(|invalid_value| InvalidValueError { name: "AD".to_string(), invalid_value, additional_info: None })?;
-        let cd = one_bit_flag::Cd::try_from(cd)
-            .map_err(|invalid_value| InvalidValueError { name: "CD".to_string(), invalid_value, additional_info: None })?;
-        let rcode = rcode::Rcode::try_from(rcode)
-            .map_err(
-                |invalid_value| {
-                    let cause = match invalid_value {
-                        rcode::FromError::LogicError(value) => (value, "logic error"),
-                        rcode::FromError::Unassigned(value) => (value, "unassigned in RFC")
-                    };
-                    InvalidValueError {
-                        name: "RCODE".to_string(),
-                        invalid_value: cause.0,
-                        additional_info: Some(cause.1.to_string()),
-                    }
-                }
-            )?;
+        let qd_zo_count = buffer.read_u16_field(Field::QdZoCount).map(count::QdZoCount)?;
+        let an_pr_count = buffer.read_u16_field(Field::AnPrCount).map(count::AnPrCount)?;
+        let ns_up_count = buffer.read_u16_field(Field::NsUpCount).map(count::NsUpCount)?;
+        let arcount     = buffer.read_u16_field(Field::Arcount).map(count::Arcount)?;
+
+        // QR, OPCODE, AA, TC and RD share the first octet of the flags, the others the second
+        let invalid = |field, value: u32| {
+            let offset = match field {
+                Field::Qr | Field::Opcode | Field::Aa | Field::Tc | Field::Rd => FLAGS_OFFSET,
+                _ => FLAGS_OFFSET + 1,
+            };
+            error::invalid_value(field, offset, value)
+        };
+        let qr = one_bit_flag::Qr::try_from(qr).map_err(|value| invalid(Field::Qr, value as u32))?;
+        let opcode = match opcode::Opcode::try_from(opcode) {
+            Ok(opcode)    => opcode,
+            Err(_) if lenient => opcode::Opcode::Unknown(opcode),
+            Err(_)        => return Err(invalid(Field::Opcode, opcode as u32)),
+        };
+        let aa = one_bit_flag::Aa::try_from(aa).map_err(|value| invalid(Field::Aa, value as u32))?;
+        let tc = one_bit_flag::Tc::try_from(tc).map_err(|value| invalid(Field::Tc, value as u32))?;
+        let rd = one_bit_flag::Rd::try_from(rd).map_err(|value| invalid(Field::Rd, value as u32))?;
+        let ra = one_bit_flag::Ra::try_from(ra).map_err(|value| invalid(Field::Ra, value as u32))?;
+        let z = match z::Z::try_from(z) {
+            Ok(z)                 => z,
+            Err(value) if lenient => z::Z::Unknown(value),
+            Err(value)            => return Err(invalid(Field::Z, value as u32)),
+        };
+        let ad = one_bit_flag::Ad::try_from(ad).map_err(|value| invalid(Field::Ad, value as u32))?;
+        let cd = one_bit_flag::Cd::try_from(cd).map_err(|value| invalid(Field::Cd, value as u32))?;
+        let rcode = match rcode::Rcode::try_from(rcode) {
+            Ok(rcode)     => rcode,
+            Err(_) if lenient => rcode::Rcode::Unknown(rcode),
+            Err(_)        => return Err(invalid(Field::Rcode, rcode as u32)),
+        };
 
         Ok(Header {
             id: id::Id(id),
@@ -168,7 +107,7 @@ impl std::convert::TryFrom<&[u8]> for Header {
             tc,
             rd,
             ra,
-            z: z::Z,
+            z,
             ad,
             cd,
             rcode,
@@ -178,25 +117,31 @@ impl std::convert::TryFrom<&[u8]> for Header {
             ns_up_count
         })
     }
-}
-
-impl std::convert::TryInto<Vec<u8>> for Header {
-    type Error = Box<dyn std::error::Error>;
 
-    fn try_into(self) -> Result<Vec<u8>, Self::Error> {
-        let mut buffer = vec![];
-
-        buffer.write_u16::<BigEndian>(self.id.into())
-            .map_err(|e| HeaderWriteError { cause: e.to_string(), property_name: "ID".to_string() })?;
+    pub(crate) fn write_to(&self, buffer: &mut impl Sink) -> Result<(), EncodeError> {
+        let start = buffer.written();
+        buffer.put_slice(&Into::<u16>::into(self.id).to_be_bytes());
         let qr: u8     = self.qr.into();
         let opcode: u8 = self.opcode.into();
         let aa: u8     = self.aa.into();
         let tc: u8     = self.tc.into();
         let rd: u8     = self.rd.into();
-        let ra: u8     = self.rd.into();
+        let ra: u8     = self.ra.into();
+        let z: u8      = self.z.into();
         let ad: u8     = self.ad.into();
         let cd: u8     = self.cd.into();
         let rcode: u16 = self.rcode.into();
+        // extended RCODEs need an OPT RR and cannot go into the 4 bits of the header
+        let too_large = [
+            (Field::Opcode, opcode as u16, 0x0F, FLAGS_OFFSET    ),
+            (Field::Z,      z as u16,      0x01, FLAGS_OFFSET + 1),
+            (Field::Rcode,  rcode,         0x0F, FLAGS_OFFSET + 1),
+        ];
+        for (field, value, max, offset) in too_large {
+            if value > max {
+                return Err(error::value_too_large(field, start + offset, value as u64));
+            }
+        }
         let chunk: u16 =
               ((qr as u16)     << 15)
             | ((opcode as u16) << 11)
@@ -204,19 +149,165 @@ impl std::convert::TryInto<Vec<u8>> for Header {
             | ((tc as u16)     <<  9)
             | ((rd as u16)     <<  8)
             | ((ra as u16)     <<  7)
+            | ((z as u16)      <<  6)
             | ((ad as u16)     <<  5)
             | ((cd as u16)     <<  4)
             | rcode;
-        buffer.write_u16::<BigEndian>(chunk)
-            .map_err(|e| HeaderWriteError { cause: e.to_string(), property_name: format!("{} ~ {} bit", 17, 32) })?;
-        buffer.write_u16::<BigEndian>(self.qd_zo_count.into())
-            .map_err(|e| HeaderWriteError { cause: e.to_string(), property_name: "QDCOUNT / ZOCOUNT".to_string() })?;
-        buffer.write_u16::<BigEndian>(self.an_pr_count.into())
-            .map_err(|e| HeaderWriteError { cause: e.to_string(), property_name: "ANCOUNT / PRCOUNT".to_string() })?;
-        buffer.write_u16::<BigEndian>(self.ns_up_count.into())
-            .map_err(|e| HeaderWriteError { cause: e.to_string(), property_name: "NSCOUNT / UPCOUNT".to_string() })?;
-        buffer.write_u16::<BigEndian>(self.arcount.into())
-            .map_err(|e| HeaderWriteError { cause: e.to_string(), property_name: "ARCOUNT".to_string() })?;
+        buffer.put_slice(&chunk.to_be_bytes());
+        buffer.put_slice(&Into::<u16>::into(self.qd_zo_count).to_be_bytes());
+        buffer.put_slice(&Into::<u16>::into(self.an_pr_count).to_be_bytes());
+        buffer.put_slice(&Into::<u16>::into(self.ns_up_count).to_be_bytes());
+        buffer.put_slice(&Into::<u16>::into(self.arcount).to_be_bytes());
+        Ok(())
+    }
+}
+
+impl Header {
+    /// Header of an outgoing query with every flag cleared; counts are filled in on encoding.
+    pub fn query(id: id::Id, opcode: opcode::Opcode) -> Self {
+        Self {
+            id,
+            qr:          one_bit_flag::Qr::Query,
+            opcode,
+            aa:          one_bit_flag::Aa::FromNonAuthority,
+            tc:          one_bit_flag::Tc::NotTruncated,
+            rd:          one_bit_flag::Rd::RecursiveUndesired,
+            ra:          one_bit_flag::Ra::RecursionUnavailable,
+            z:           z::Z::Zero,
+            ad:          one_bit_flag::Ad::DnssecValidationFailureOrNotSupportedAdBit,
+            cd:          one_bit_flag::Cd::DnssecEnabled,
+            rcode:       rcode::Rcode::NoError,
+            qd_zo_count: count::QdZoCount(0),
+            an_pr_count: count::AnPrCount(0),
+            ns_up_count: count::NsUpCount(0),
+            arcount:     count::Arcount(0),
+        }
+    }
+
+    /// Header of a response to `query`: ID, OPCODE, RD and CD are copied and the other flags cleared.
+    pub fn response(query: &Header) -> Self {
+        Self {
+            qr: one_bit_flag::Qr::Response,
+            rd: query.rd,
+            cd: query.cd,
+            ..Self::query(query.id, query.opcode)
+        }
+    }
+
+    pub fn builder() -> builder::HeaderBuilder {
+        builder::HeaderBuilder::default()
+    }
+
+    pub fn is_response(&self) -> bool {
+        self.qr.into()
+    }
+
+    pub fn set_response(&mut self, value: bool) {
+        self.qr = value.into();
+    }
+
+    pub fn authoritative(&self) -> bool {
+        self.aa.into()
+    }
+
+    pub fn set_authoritative(&mut self, value: bool) {
+        self.aa = value.into();
+    }
+
+    pub fn truncated(&self) -> bool {
+        self.tc.into()
+    }
+
+    pub fn set_truncated(&mut self, value: bool) {
+        self.tc = value.into();
+    }
+
+    pub fn recursion_desired(&self) -> bool {
+        self.rd.into()
+    }
+
+    pub fn set_recursion_desired(&mut self, value: bool) {
+        self.rd = value.into();
+    }
+
+    pub fn recursion_available(&self) -> bool {
+        self.ra.into()
+    }
+
+    pub fn set_recursion_available(&mut self, value: bool) {
+        self.ra = value.into();
+    }
+
+    pub fn authentic_data(&self) -> bool {
+        self.ad.into()
+    }
+
+    pub fn set_authentic_data(&mut self, value: bool) {
+        self.ad = value.into();
+    }
+
+    pub fn checking_disabled(&self) -> bool {
+        self.cd.into()
+    }
+
+    pub fn set_checking_disabled(&mut self, value: bool) {
+        self.cd = value.into();
+    }
+}
+
+impl Header {
+    /// Encodes into `sink`, returning the number of bytes written.
+    pub fn encode_to(&self, sink: &mut impl Sink) -> Result<usize, EncodeError> {
+        self.write_to(sink)?;
+        Ok(HEADER_LENGTH)
+    }
+
+    /// Encodes into `buffer`, returning the number of bytes written.
+    pub fn encode_into(&self, buffer: &mut [u8]) -> Result<usize, EncodeError> {
+        sink::encode_into(buffer, |sink| self.encode_to(sink))
+    }
+}
+
+/// The two header lines dig prints, such as `;; ->>HEADER<<- opcode: QUERY, status: NOERROR, id: 43981`
+/// and `;; flags: qr rd; QUERY: 1, ANSWER: 0, AUTHORITY: 0, ADDITIONAL: 0`.
+impl core::fmt::Display for Header {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(f, ";; ->>HEADER<<- opcode: {}, status: {}, id: {}", self.opcode, self.rcode, Into::<u16>::into(self.id))?;
+
+        f.write_str(";; flags:")?;
+        let flags = [
+            ("qr", self.is_response()),
+            ("aa", self.authoritative()),
+            ("tc", self.truncated()),
+            ("rd", self.recursion_desired()),
+            ("ra", self.recursion_available()),
+            ("ad", self.authentic_data()),
+            ("cd", self.checking_disabled()),
+        ];
+        for (flag, _) in flags.iter().filter(|(_, set)| *set) {
+            write!(f, " {}", flag)?;
+        }
+        let sections = match self.opcode {
+            opcode::Opcode::Update => ["ZONE", "PREREQ", "UPDATE", "ADDITIONAL"],
+            _                      => ["QUERY", "ANSWER", "AUTHORITY", "ADDITIONAL"],
+        };
+        write!(
+            f, "; {}: {}, {}: {}, {}: {}, {}: {}",
+            sections[0], Into::<u16>::into(self.qd_zo_count),
+            sections[1], Into::<u16>::into(self.an_pr_count),
+            sections[2], Into::<u16>::into(self.ns_up_count),
+            sections[3], Into::<u16>::into(self.arcount),
+        )
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::convert::TryInto<alloc::vec::Vec<u8>> for Header {
+    type Error = EncodeError;
+
+    fn try_into(self) -> Result<alloc::vec::Vec<u8>, Self::Error> {
+        let mut buffer = alloc::vec![];
+        self.encode_to(&mut buffer)?;
 
         Ok(buffer)
     }
@@ -259,7 +350,7 @@ mod tests {
             tc:          one_bit_flag::Tc::Truncated,
             rd:          one_bit_flag::Rd::RecursiveUndesired,
             ra:          one_bit_flag::Ra::RecursionAvailable,
-            z:           z::Z,
+            z:           z::Z::Zero,
             ad:          one_bit_flag::Ad::SuccessDnssecValidationOrSupportedAdBit,
             cd:          one_bit_flag::Cd::DnssecEnabled,
             rcode:       rcode::Rcode::NoError,
@@ -295,7 +386,11 @@ mod tests {
             0x03, 0x04, // NSCOUNT / UPCOUNT = 0x0304
             0x05,       // ARCOUNT (!!!LACK!!! length == 16bit == 2byte)
         ];
-        assert!(Header::try_from(buffer).is_err(), "should not decode lack slidce");
+        assert_eq!(
+            Header::try_from(buffer).unwrap_err(),
+            DecodeError::Truncated { section: error::Section::Header, field: Field::Arcount, offset: 10 },
+            "should not decode lack slidce",
+        );
     }
 
     #[test]
@@ -319,7 +414,11 @@ mod tests {
             0x03, 0x04, // NSCOUNT / UPCOUNT = 0x0304
             0x05, 0x06, // ARCOUNT           = 0x0506
         ];
-        assert!(Header::try_from(buffer).is_err(), "should not decode invalid DNS packet (OPCODE)");
+        assert_eq!(
+            Header::try_from(buffer).unwrap_err(),
+            DecodeError::InvalidValue { section: error::Section::Header, field: Field::Opcode, offset: 2, value: 7 },
+            "should not decode invalid DNS packet (OPCODE)",
+        );
 
         let buffer: &[u8] = &[
             0xAB, 0xCD, // ID = 0xABCD
@@ -340,9 +439,14 @@ mod tests {
             0x03, 0x04, // NSCOUNT / UPCOUNT = 0x0304
             0x05, 0x06, // ARCOUNT           = 0x0506
         ];
-        assert!(Header::try_from(buffer).is_err(), "should not decode invalid DNS packet (RCODE)");
+        assert_eq!(
+            Header::try_from(buffer).unwrap_err(),
+            DecodeError::InvalidValue { section: error::Section::Header, field: Field::Rcode, offset: 3, value: 12 },
+            "should not decode invalid DNS packet (RCODE)",
+        );
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn it_encodes_to_u8_array() {
         let header = Header {
@@ -353,7 +457,7 @@ mod tests {
             tc:          one_bit_flag::Tc::NotTruncated,
             rd:          one_bit_flag::Rd::RecursiveUndesired,
             ra:          one_bit_flag::Ra::RecursionUnavailable,
-            z:           z::Z,
+            z:           z::Z::Zero,
             ad:          one_bit_flag::Ad::SuccessDnssecValidationOrSupportedAdBit,
             cd:          one_bit_flag::Cd::DnssecForbidden,
             rcode:       rcode::Rcode::NoError,
@@ -386,4 +490,100 @@ mod tests {
         let encoded_buffer: Vec<u8> = encoded_buffer.unwrap();
         assert_eq!(encoded_buffer, expected_buffer);
     }
+
+    #[test]
+    fn it_encodes_rd_and_ra_to_own_bits() {
+        let header = Header {
+            rd: one_bit_flag::Rd::RecursiveDesired,
+            ra: one_bit_flag::Ra::RecursionUnavailable,
+            ..Header::query(id::Id(0xABCD), opcode::Opcode::Query)
+        };
+        let mut encoded = [0; HEADER_LENGTH];
+        header.encode_into(&mut encoded).unwrap();
+        assert_eq!((encoded[2] & 0x01, encoded[3] & 0x80), (0x01, 0x00));
+
+        let header = Header {
+            rd: one_bit_flag::Rd::RecursiveUndesired,
+            ra: one_bit_flag::Ra::RecursionAvailable,
+            ..Header::query(id::Id(0xABCD), opcode::Opcode::Query)
+        };
+        let mut encoded = [0; HEADER_LENGTH];
+        header.encode_into(&mut encoded).unwrap();
+        assert_eq!((encoded[2] & 0x01, encoded[3] & 0x80), (0x00, 0x80));
+    }
+
+    #[test]
+    #[allow(clippy::unusual_byte_groupings)]
+    fn it_decodes_nonstandard_header_leniently() {
+        let buffer: &[u8] = &[
+            0xAB, 0xCD, // ID = 0xABCD
+            0b1_0111_0_0_1, 0b1_1_0_0_1100,
+            //^ ^    ^ ^ ^    ^ ^ ^ ^ ^
+            //| |    | | |    | | | | `---- RCODE  = unassigned value 12
+            //| |    | | |    | | | `------ CD     = DNSSEC enabled
+            //| |    | | |    | | `-------- AD     = DNSSEC validation failure / not supported AD bit
+            //| |    | | |    | `---------- Z      = nonzero
+            //| |    | | |    `------------ RA     = Supported recuesive query
+            //| |    | | `----------------- RD     = Server recursion desired
+            //| |    | `------------------- TC     = Not truncated
+            //| |    `--------------------- AA     = from Non-Authoritative Server
+            //| `-------------------------- OPCODE = unassigned value 7
+            //`---------------------------- QR     = Response
+            0x00, 0x01, // QDCOUNT / ZOCOUNT = 0x0001
+            0x01, 0x02, // ANCOUNT / PRCOUNT = 0x0102
+            0x03, 0x04, // NSCOUNT / UPCOUNT = 0x0304
+            0x05, 0x06, // ARCOUNT           = 0x0506
+        ];
+        assert!(Header::try_from(buffer).is_err(), "should keep strict decoding by default");
+
+        let header = Header::try_from_lenient(buffer).unwrap();
+        assert_eq!(header.opcode, opcode::Opcode::Unknown(7));
+        assert_eq!(header.z,      z::Z::Unknown(1)          );
+        assert_eq!(header.rcode,  rcode::Rcode::Unknown(12) );
+        assert_eq!(header.ra,     one_bit_flag::Ra::RecursionAvailable);
+        let mut encoded = [0; 12];
+        assert_eq!(header.encode_into(&mut encoded), Ok(12));
+        assert_eq!(encoded, buffer);
+
+        let buffer: &[u8] = &[0xAB, 0xCD, 0x01];
+        assert!(Header::try_from_lenient(buffer).is_err(), "should not decode lack slice");
+    }
+
+    #[test]
+    fn it_reports_error_when_rcode_needs_extension() {
+        let buffer: &[u8] = &[0xAB, 0xCD, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let header = Header { rcode: rcode::Rcode::Badcookie, ..Header::try_from(buffer).unwrap() };
+        assert_eq!(header.encode_into(&mut [0; 12]), Err(EncodeError::ValueTooLarge { section: error::Section::Header, field: Field::Rcode, offset: 3, value: 23 }));
+    }
+
+    #[test]
+    fn it_builds_response_header_from_query() {
+        let mut query = Header::query(id::Id(0xABCD), opcode::Opcode::Notify);
+        query.set_recursion_desired(true);
+        query.set_checking_disabled(true);
+        query.set_authoritative(true);
+        let response = Header::response(&query);
+        assert!(response.is_response());
+        assert_eq!(response.id,     id::Id(0xABCD)          );
+        assert_eq!(response.opcode, opcode::Opcode::Notify  );
+        assert!(response.recursion_desired());
+        assert!(response.checking_disabled());
+        assert!(!response.authoritative());
+        assert_eq!(response.cd, one_bit_flag::Cd::DnssecForbidden);
+    }
+
+    #[test]
+    fn it_displays_like_dig() {
+        let mut header = Header::query(id::Id(43981), opcode::Opcode::Query);
+        header.set_response(true);
+        header.set_authoritative(true);
+        header.set_recursion_desired(true);
+        header.qd_zo_count = count::QdZoCount(1);
+        header.an_pr_count = count::AnPrCount(1);
+        assert_eq!(
+            header.to_string(),
+            ";; ->>HEADER<<- opcode: QUERY, status: NOERROR, id: 43981\n\
+             ;; flags: qr aa rd; QUERY: 1, ANSWER: 1, AUTHORITY: 0, ADDITIONAL: 0",
+        );
+    }
 }
@@ -0,0 +1,93 @@
+use super::{id, opcode, rcode, Header};
+
+/// Assembles a [`Header`] starting from a query with every flag cleared.
+///
+/// The counts stay zero; the message encoder fills them in from the sections.
+#[derive(Debug, Clone, Copy)]
+pub struct HeaderBuilder {
+    header: Header,
+}
+
+impl Default for HeaderBuilder {
+    fn default() -> Self {
+        Self { header: Header::query(id::Id(0), opcode::Opcode::Query) }
+    }
+}
+
+impl HeaderBuilder {
+    pub fn id(mut self, id: id::Id) -> Self {
+        self.header.id = id;
+        self
+    }
+
+    pub fn opcode(mut self, opcode: opcode::Opcode) -> Self {
+        self.header.opcode = opcode;
+        self
+    }
+
+    pub fn rcode(mut self, rcode: rcode::Rcode) -> Self {
+        self.header.rcode = rcode;
+        self
+    }
+
+    pub fn response(mut self, value: bool) -> Self {
+        self.header.set_response(value);
+        self
+    }
+
+    pub fn authoritative(mut self, value: bool) -> Self {
+        self.header.set_authoritative(value);
+        self
+    }
+
+    pub fn truncated(mut self, value: bool) -> Self {
+        self.header.set_truncated(value);
+        self
+    }
+
+    pub fn recursion_desired(mut self, value: bool) -> Self {
+        self.header.set_recursion_desired(value);
+        self
+    }
+
+    pub fn recursion_available(mut self, value: bool) -> Self {
+        self.header.set_recursion_available(value);
+        self
+    }
+
+    pub fn authentic_data(mut self, value: bool) -> Self {
+        self.header.set_authentic_data(value);
+        self
+    }
+
+    pub fn checking_disabled(mut self, value: bool) -> Self {
+        self.header.set_checking_disabled(value);
+        self
+    }
+
+    pub fn build(self) -> Header {
+        self.header
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::one_bit_flag;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_builds_header_with_defaults() {
+        let header = Header::builder()
+            .id(id::Id(0x1234))
+            .recursion_desired(true)
+            .build();
+        assert_eq!(header, Header {
+            rd: one_bit_flag::Rd::RecursiveDesired,
+            ..Header::query(id::Id(0x1234), opcode::Opcode::Query)
+        });
+        assert!(!header.is_response());
+        assert!(header.recursion_desired());
+        assert!(!header.truncated());
+    }
+}
@@ -1,12 +1,12 @@
 macro_rules! implement_u16_encoder_and_decoder {
     ($struct_name:ident) => {
-        impl std::convert::From<u16> for $struct_name {
+        impl core::convert::From<u16> for $struct_name {
             fn from(value: u16) -> Self {
                 $struct_name(value)
             }
         }
 
-        impl std::convert::Into<u16> for $struct_name {
+        impl core::convert::Into<u16> for $struct_name {
             fn into(self) -> u16 {
                 let $struct_name(value) = self;
                 value
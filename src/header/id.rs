@@ -1,13 +1,13 @@
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Id(pub u16);
 
-impl std::convert::Into<u16> for Id {
+impl core::convert::Into<u16> for Id {
     fn into(self) -> u16 {
         self.0
     }
 }
 
-impl std::convert::From<u16> for Id {
+impl core::convert::From<u16> for Id {
     fn from(value: u16) -> Self {
         Self(value)
     }
@@ -1,6 +1,6 @@
 macro_rules! implement_u8_encoder_and_decoder {
     ($enum_name:ty, $zero_value:path, $one_value:path $(,)?) => {
-        impl std::convert::TryFrom<u8> for $enum_name {
+        impl core::convert::TryFrom<u8> for $enum_name {
             type Error = u8;
 
             fn try_from(value: u8) -> Result<Self, Self::Error> {
@@ -12,7 +12,7 @@ macro_rules! implement_u8_encoder_and_decoder {
             }
         }
 
-        impl std::convert::Into<u8> for $enum_name {
+        impl core::convert::Into<u8> for $enum_name {
             fn into(self) -> u8 {
                 match self {
                     $zero_value => 0,
@@ -22,6 +22,54 @@ macro_rules! implement_u8_encoder_and_decoder {
         }
     };    
 }
+
+/// Maps the set bit to `true`, for the boolean accessors of [`Header`](super::Header).
+macro_rules! implement_bool_conversion {
+    ($enum_name:ty, $zero_value:path, $one_value:path $(,)?) => {
+        impl core::convert::From<bool> for $enum_name {
+            fn from(value: bool) -> Self {
+                if value { $one_value } else { $zero_value }
+            }
+        }
+
+        impl core::convert::From<$enum_name> for bool {
+            fn from(value: $enum_name) -> Self {
+                Into::<u8>::into(value) == 1
+            }
+        }
+    };
+}
+
+/// Prints the flag as dig names it, such as `rd`, or prefixed with `no` when it is cleared, as in
+/// dig's `+nord`. Parsing accepts both forms in any case.
+macro_rules! implement_mnemonic {
+    ($enum_name:ty, $mnemonic:literal, $kind:literal) => {
+        impl core::fmt::Display for $enum_name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let set: bool = (*self).into();
+                if !set {
+                    f.write_str("no")?;
+                }
+                f.write_str($mnemonic)
+            }
+        }
+
+        impl core::str::FromStr for $enum_name {
+            type Err = crate::error::UnknownMnemonic;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let (set, flag) = match s.get(..2) {
+                    Some(prefix) if prefix.eq_ignore_ascii_case("no") => (false, &s[2..]),
+                    _                                                 => (true, s),
+                };
+                if !flag.eq_ignore_ascii_case($mnemonic) {
+                    return Err(crate::error::UnknownMnemonic { kind: $kind });
+                }
+                Ok(Self::from(set))
+            }
+        }
+    };
+}
 #[cfg(test)]
 mod implement_u8_encoder_and_decoder_tests {
     use pretty_assertions::assert_eq;
@@ -37,6 +85,11 @@ mod implement_u8_encoder_and_decoder_tests {
         OneBitFlagMock::ZeroValue,
         OneBitFlagMock::OneValue,
     );
+    implement_bool_conversion!(
+        OneBitFlagMock,
+        OneBitFlagMock::ZeroValue,
+        OneBitFlagMock::OneValue,
+    );
 
     #[test]
     fn it_converts_to_valid_u8_value() {
@@ -56,6 +109,12 @@ mod implement_u8_encoder_and_decoder_tests {
     fn it_does_not_convert_from_invalid_u8_value() {
         assert_eq!(OneBitFlagMock::try_from(2_u8), Err(2_u8));
     }
+
+    #[test]
+    fn it_converts_between_bool_and_bit() {
+        assert_eq!(OneBitFlagMock::from(true), OneBitFlagMock::OneValue);
+        assert_eq!(bool::from(OneBitFlagMock::ZeroValue), false);
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -68,6 +127,12 @@ implement_u8_encoder_and_decoder!(
     Self::Query,
     Self::Response,
 );
+implement_mnemonic!(Qr, "qr", "QR");
+implement_bool_conversion!(
+    Qr,
+    Self::Query,
+    Self::Response,
+);
 #[cfg(test)]
 mod qr_flag_tests {
     use super::*;
@@ -90,6 +155,12 @@ implement_u8_encoder_and_decoder!(
     Self::FromNonAuthority,
     Self::FromAuthority,
 );
+implement_mnemonic!(Aa, "aa", "AA");
+implement_bool_conversion!(
+    Aa,
+    Self::FromNonAuthority,
+    Self::FromAuthority,
+);
 #[cfg(test)]
 mod aa_flag_tests {
     use super::*;
@@ -112,12 +183,13 @@ implement_u8_encoder_and_decoder!(
     Tc::NotTruncated,
     Tc::Truncated,
 );
-impl std::convert::From<bool> for Tc {
+implement_mnemonic!(Tc, "tc", "TC");
+impl core::convert::From<bool> for Tc {
     fn from(value: bool) -> Self {
         if value { Self::Truncated } else { Self::NotTruncated }
     }
 }
-impl std::convert::Into<bool> for Tc {
+impl core::convert::Into<bool> for Tc {
     fn into(self) -> bool {
         match self {
             Self::Truncated    => true,
@@ -161,11 +233,26 @@ implement_u8_encoder_and_decoder!(
     Self::RecursiveUndesired,
     Self::RecursiveDesired,
 );
+implement_mnemonic!(Rd, "rd", "RD");
+implement_bool_conversion!(
+    Rd,
+    Self::RecursiveUndesired,
+    Self::RecursiveDesired,
+);
 #[cfg(test)]
 mod rd_flag_tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn it_parses_and_displays_mnemonic() {
+        assert_eq!("rd".parse(),       Ok(Rd::RecursiveDesired)                           );
+        assert_eq!("NORD".parse(),     Ok(Rd::RecursiveUndesired)                         );
+        assert_eq!("ra".parse::<Rd>(), Err(crate::error::UnknownMnemonic { kind: "RD" }));
+        assert_eq!(Rd::RecursiveDesired.to_string(),   "rd"  );
+        assert_eq!(Rd::RecursiveUndesired.to_string(), "nord");
+    }
+
     #[test]
     fn it_converts_to_raw_value() {
         let value: u8 = Rd::RecursiveUndesired.into();
@@ -183,12 +270,13 @@ implement_u8_encoder_and_decoder!(
     Self::RecursionUnavailable,
     Self::RecursionAvailable,
 );
-impl std::convert::From<bool> for Ra {
+implement_mnemonic!(Ra, "ra", "RA");
+impl core::convert::From<bool> for Ra {
     fn from(value: bool) -> Self {
         if value { Self::RecursionAvailable } else { Self::RecursionUnavailable }
     }
 }
-impl std::convert::Into<bool> for Ra {
+impl core::convert::Into<bool> for Ra {
     fn into(self) -> bool {
         match self {
             Self::RecursionAvailable   => true,
@@ -232,6 +320,12 @@ implement_u8_encoder_and_decoder!(
     Self::DnssecValidationFailureOrNotSupportedAdBit,
     Self::SuccessDnssecValidationOrSupportedAdBit,
 );
+implement_mnemonic!(Ad, "ad", "AD");
+implement_bool_conversion!(
+    Ad,
+    Self::DnssecValidationFailureOrNotSupportedAdBit,
+    Self::SuccessDnssecValidationOrSupportedAdBit,
+);
 #[cfg(test)]
 mod ad_flag_tests {
     use super::*;
@@ -254,6 +348,12 @@ implement_u8_encoder_and_decoder!(
     Self::DnssecEnabled,
     Self::DnssecForbidden,
 );
+implement_mnemonic!(Cd, "cd", "CD");
+implement_bool_conversion!(
+    Cd,
+    Self::DnssecEnabled,
+    Self::DnssecForbidden,
+);
 #[cfg(test)]
 mod cd_flag_tests {
     use super::*;
@@ -265,3 +365,33 @@ mod cd_flag_tests {
         assert_eq!(value, 0);
     }
 }
+
+#[cfg(test)]
+mod mnemonic_tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn round_trip<Flag>(flag: Flag) -> Result<Flag, crate::error::UnknownMnemonic>
+        where Flag: core::fmt::Display + core::str::FromStr<Err = crate::error::UnknownMnemonic>
+    {
+        flag.to_string().parse()
+    }
+
+    #[test]
+    fn it_parses_what_it_displays() {
+        assert_eq!(round_trip(Qr::Query),                                      Ok(Qr::Query)                                     );
+        assert_eq!(round_trip(Qr::Response),                                   Ok(Qr::Response)                                  );
+        assert_eq!(round_trip(Aa::FromAuthority),                              Ok(Aa::FromAuthority)                             );
+        assert_eq!(round_trip(Aa::FromNonAuthority),                           Ok(Aa::FromNonAuthority)                          );
+        assert_eq!(round_trip(Tc::Truncated),                                  Ok(Tc::Truncated)                                 );
+        assert_eq!(round_trip(Tc::NotTruncated),                               Ok(Tc::NotTruncated)                              );
+        assert_eq!(round_trip(Rd::RecursiveDesired),                           Ok(Rd::RecursiveDesired)                          );
+        assert_eq!(round_trip(Rd::RecursiveUndesired),                         Ok(Rd::RecursiveUndesired)                        );
+        assert_eq!(round_trip(Ra::RecursionUnavailable),                       Ok(Ra::RecursionUnavailable)                      );
+        assert_eq!(round_trip(Ra::RecursionAvailable),                         Ok(Ra::RecursionAvailable)                        );
+        assert_eq!(round_trip(Ad::SuccessDnssecValidationOrSupportedAdBit),    Ok(Ad::SuccessDnssecValidationOrSupportedAdBit)   );
+        assert_eq!(round_trip(Ad::DnssecValidationFailureOrNotSupportedAdBit), Ok(Ad::DnssecValidationFailureOrNotSupportedAdBit));
+        assert_eq!(round_trip(Cd::DnssecEnabled),                              Ok(Cd::DnssecEnabled)                             );
+        assert_eq!(round_trip(Cd::DnssecForbidden),                            Ok(Cd::DnssecForbidden)                           );
+    }
+}
@@ -1,3 +1,6 @@
+use crate::error::UnknownMnemonic;
+use crate::presentation;
+
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Opcode {
@@ -7,12 +10,48 @@ pub enum Opcode {
     Notify,
     Update,
     DnsStatefulOperations,
+    /// Unassigned value kept by lenient decoding.
+    Unknown(u8),
 }
 
 impl Opcode {
-    pub fn to_hex_string(&self) -> String {
+    #[cfg(feature = "alloc")]
+    pub fn to_hex_string(&self) -> alloc::string::String {
         let value: u8 = self.clone().into();
-        format!("{:02X}", value)
+        alloc::format!("{:02X}", value)
+    }
+}
+
+/// IANA mnemonics, as printed by dig.
+const MNEMONICS: [(&str, Opcode); 6] = [
+    ("QUERY",  Opcode::Query                ),
+    ("IQUERY", Opcode::InverseQuery         ),
+    ("STATUS", Opcode::ServerStatus         ),
+    ("NOTIFY", Opcode::Notify               ),
+    ("UPDATE", Opcode::Update               ),
+    ("DSO",    Opcode::DnsStatefulOperations),
+];
+
+/// Mnemonic, or the value in decimal for opcodes without one.
+impl core::fmt::Display for Opcode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let value: u8 = (*self).into();
+        presentation::fmt_mnemonic(presentation::mnemonic_of(&MNEMONICS, self), value, f)
+    }
+}
+
+/// Accepts the mnemonics in any case, and values from 0 to 15 in decimal.
+impl core::str::FromStr for Opcode {
+    type Err = UnknownMnemonic;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(opcode) = presentation::from_mnemonic(&MNEMONICS, s) {
+            return Ok(opcode);
+        }
+        match s.parse::<u8>() {
+            Ok(value) if value <= 0x0F => Ok(Self::try_from(value).unwrap_or(Self::Unknown(value))),
+            _                          => Err(UnknownMnemonic { kind: "OPCODE" }),
+        }
     }
 }
 
@@ -23,7 +62,7 @@ pub enum FromError {
     InvalidRange(u8),
 }
 
-impl std::convert::TryFrom<u8> for Opcode {
+impl core::convert::TryFrom<u8> for Opcode {
     type Error = FromError;
 
     fn try_from(value: u8) -> Result<Self, Self::Error> {
@@ -40,7 +79,7 @@ impl std::convert::TryFrom<u8> for Opcode {
     }
 }
 
-impl std::convert::Into<u8> for Opcode {
+impl core::convert::Into<u8> for Opcode {
     fn into(self) -> u8 {
         match self {
             Self::Query                 => 0,
@@ -49,6 +88,7 @@ impl std::convert::Into<u8> for Opcode {
             Self::Notify                => 4,
             Self::Update                => 5,
             Self::DnsStatefulOperations => 6,
+            Self::Unknown(v)            => v,
         }
     }
 }
@@ -72,6 +112,8 @@ mod tests {
         assert_eq!(value, 5);
         let value: u8 = Opcode::DnsStatefulOperations.into();
         assert_eq!(value, 6);
+        let value: u8 = Opcode::Unknown(7).into();
+        assert_eq!(value, 7);
     }
 
     #[test]
@@ -91,6 +133,19 @@ mod tests {
         assert_eq!(Opcode::try_from(16_u8), Err(FromError::InvalidRange(16_u8)));
     }
 
+    #[test]
+    fn it_parses_and_displays_mnemonic() {
+        assert_eq!("QUERY".parse(),            Ok(Opcode::Query)                       );
+        assert_eq!("dso".parse(),              Ok(Opcode::DnsStatefulOperations)       );
+        assert_eq!("4".parse(),                Ok(Opcode::Notify)                      );
+        assert_eq!("7".parse(),                Ok(Opcode::Unknown(7))                  );
+        assert_eq!("16".parse::<Opcode>(),     Err(UnknownMnemonic { kind: "OPCODE" }) );
+        assert_eq!("QUERYX".parse::<Opcode>(), Err(UnknownMnemonic { kind: "OPCODE" }) );
+        assert_eq!(Opcode::InverseQuery.to_string(), "IQUERY");
+        assert_eq!(Opcode::Unknown(7).to_string(),   "7"     );
+    }
+
+    #[cfg(feature = "alloc")]
     #[test]
     fn it_converts_to_hex_string() {
         assert_eq!(Opcode::Query.to_hex_string(),                 "00");
@@ -99,6 +154,7 @@ mod tests {
         assert_eq!(Opcode::Notify.to_hex_string(),                "04");
         assert_eq!(Opcode::Update.to_hex_string(),                "05");
         assert_eq!(Opcode::DnsStatefulOperations.to_hex_string(), "06");
+        assert_eq!(Opcode::Unknown(15).to_hex_string(),           "0F");
     }
 }
 
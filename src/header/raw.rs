@@ -0,0 +1,239 @@
+use core::borrow::{Borrow, BorrowMut};
+use crate::error::{self, DecodeError, EncodeError, Field};
+use super::{count, id, one_bit_flag, opcode, rcode, HEADER_LENGTH, FLAGS_OFFSET};
+
+macro_rules! flag_getter {
+    ($getter:ident, $flag:ty, $shift:expr) => {
+        pub fn $getter(&self) -> $flag {
+            <$flag>::from((self.flags() >> $shift) & 1 == 1)
+        }
+    };
+}
+
+macro_rules! flag_setter {
+    ($setter:ident, $flag:ty, $shift:expr) => {
+        pub fn $setter(&mut self, value: $flag) {
+            let value: bool = value.into();
+            self.set_flags(1 << $shift, (value as u16) << $shift);
+        }
+    };
+}
+
+/// Header fields read and written in place on the wire bytes, without decoding into [`Header`](super::Header).
+///
+/// OPCODE and RCODE values without a variant read as `Unknown`, like [`Header::try_from_lenient`](super::Header::try_from_lenient).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawHeader<Buffer> {
+    buffer: Buffer,
+}
+
+impl<Buffer> RawHeader<Buffer> {
+    pub fn new(buffer: Buffer) -> Self {
+        Self { buffer }
+    }
+
+    pub fn into_inner(self) -> Buffer {
+        self.buffer
+    }
+}
+
+impl<'a> core::convert::TryFrom<&'a [u8]> for RawHeader<&'a [u8; HEADER_LENGTH]> {
+    type Error = DecodeError;
+
+    /// Wraps the header at the start of a message.
+    fn try_from(message: &'a [u8]) -> Result<Self, Self::Error> {
+        message.first_chunk()
+            .map(Self::new)
+            .ok_or_else(|| truncated(message.len()))
+    }
+}
+
+impl<'a> core::convert::TryFrom<&'a mut [u8]> for RawHeader<&'a mut [u8; HEADER_LENGTH]> {
+    type Error = DecodeError;
+
+    /// Wraps the header at the start of a message.
+    fn try_from(message: &'a mut [u8]) -> Result<Self, Self::Error> {
+        let length = message.len();
+        message.first_chunk_mut()
+            .map(Self::new)
+            .ok_or_else(|| truncated(length))
+    }
+}
+
+/// Reports the field a message of `length` bytes ends inside.
+fn truncated(length: usize) -> DecodeError {
+    let field = match length {
+        0..=1   => Field::Id,
+        2..=3   => Field::Qr,
+        4..=5   => Field::QdZoCount,
+        6..=7   => Field::AnPrCount,
+        8..=9   => Field::NsUpCount,
+        _       => Field::Arcount,
+    };
+    error::truncated(field, length & !1)
+}
+
+impl<Buffer: Borrow<[u8; HEADER_LENGTH]>> RawHeader<Buffer> {
+    fn u16_at(&self, offset: usize) -> u16 {
+        let bytes = self.buffer.borrow();
+        u16::from_be_bytes([bytes[offset], bytes[offset + 1]])
+    }
+
+    fn flags(&self) -> u16 {
+        self.u16_at(FLAGS_OFFSET)
+    }
+
+    pub fn id(&self) -> id::Id {
+        id::Id(self.u16_at(0))
+    }
+
+    flag_getter!(qr, one_bit_flag::Qr, 15);
+    flag_getter!(aa, one_bit_flag::Aa, 10);
+    flag_getter!(tc, one_bit_flag::Tc,  9);
+    flag_getter!(rd, one_bit_flag::Rd,  8);
+    flag_getter!(ra, one_bit_flag::Ra,  7);
+    flag_getter!(ad, one_bit_flag::Ad,  5);
+    flag_getter!(cd, one_bit_flag::Cd,  4);
+
+    pub fn opcode(&self) -> opcode::Opcode {
+        let value = ((self.flags() >> 11) & 0x0F) as u8;
+        opcode::Opcode::try_from(value).unwrap_or(opcode::Opcode::Unknown(value))
+    }
+
+    pub fn rcode(&self) -> rcode::Rcode {
+        let value = self.flags() & 0x0F;
+        rcode::Rcode::try_from(value).unwrap_or(rcode::Rcode::Unknown(value))
+    }
+
+    pub fn qd_zo_count(&self) -> count::QdZoCount {
+        count::QdZoCount(self.u16_at(4))
+    }
+
+    pub fn an_pr_count(&self) -> count::AnPrCount {
+        count::AnPrCount(self.u16_at(6))
+    }
+
+    pub fn ns_up_count(&self) -> count::NsUpCount {
+        count::NsUpCount(self.u16_at(8))
+    }
+
+    pub fn arcount(&self) -> count::Arcount {
+        count::Arcount(self.u16_at(10))
+    }
+}
+
+impl<Buffer: BorrowMut<[u8; HEADER_LENGTH]>> RawHeader<Buffer> {
+    fn set_u16_at(&mut self, offset: usize, value: u16) {
+        self.buffer.borrow_mut()[offset..offset + 2].copy_from_slice(&value.to_be_bytes());
+    }
+
+    /// Replaces the bits of the flags selected by `mask` with those of `value`.
+    fn set_flags(&mut self, mask: u16, value: u16) {
+        let flags = (self.flags() & !mask) | (value & mask);
+        self.set_u16_at(FLAGS_OFFSET, flags);
+    }
+
+    flag_setter!(set_qr, one_bit_flag::Qr, 15);
+    flag_setter!(set_aa, one_bit_flag::Aa, 10);
+    flag_setter!(set_tc, one_bit_flag::Tc,  9);
+    flag_setter!(set_rd, one_bit_flag::Rd,  8);
+    flag_setter!(set_ra, one_bit_flag::Ra,  7);
+    flag_setter!(set_ad, one_bit_flag::Ad,  5);
+    flag_setter!(set_cd, one_bit_flag::Cd,  4);
+
+    pub fn set_id(&mut self, id: id::Id) {
+        self.set_u16_at(0, id.into());
+    }
+
+    pub fn set_opcode(&mut self, opcode: opcode::Opcode) -> Result<(), EncodeError> {
+        let value: u8 = opcode.into();
+        if value > 0x0F {
+            return Err(error::value_too_large(Field::Opcode, FLAGS_OFFSET, value as u64));
+        }
+        self.set_flags(0x0F << 11, (value as u16) << 11);
+        Ok(())
+    }
+
+    /// Extended RCODEs need an OPT RR and are rejected like [`Header`](super::Header) encoding does.
+    pub fn set_rcode(&mut self, rcode: rcode::Rcode) -> Result<(), EncodeError> {
+        let value: u16 = rcode.into();
+        if value > 0x0F {
+            return Err(error::value_too_large(Field::Rcode, FLAGS_OFFSET + 1, value as u64));
+        }
+        self.set_flags(0x0F, value);
+        Ok(())
+    }
+
+    pub fn set_qd_zo_count(&mut self, count: count::QdZoCount) {
+        self.set_u16_at(4, count.into());
+    }
+
+    pub fn set_an_pr_count(&mut self, count: count::AnPrCount) {
+        self.set_u16_at(6, count.into());
+    }
+
+    pub fn set_ns_up_count(&mut self, count: count::NsUpCount) {
+        self.set_u16_at(8, count.into());
+    }
+
+    pub fn set_arcount(&mut self, count: count::Arcount) {
+        self.set_u16_at(10, count.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::Header;
+    use pretty_assertions::assert_eq;
+
+    fn query() -> [u8; 14] {
+        let mut message = [0; 14];
+        let header = Header { rd: one_bit_flag::Rd::RecursiveDesired, ..Header::query(id::Id(0xABCD), opcode::Opcode::Query) };
+        header.encode_into(&mut message[..HEADER_LENGTH]).unwrap();
+        message
+    }
+
+    #[test]
+    fn it_reads_fields_in_place() {
+        let message = query();
+        let header = RawHeader::try_from(&message[..]).unwrap();
+        assert_eq!(header.id(),          id::Id(0xABCD)                    );
+        assert_eq!(header.qr(),          one_bit_flag::Qr::Query           );
+        assert_eq!(header.opcode(),      opcode::Opcode::Query             );
+        assert_eq!(header.rd(),          one_bit_flag::Rd::RecursiveDesired);
+        assert_eq!(header.rcode(),       rcode::Rcode::NoError             );
+        assert_eq!(header.qd_zo_count(), count::QdZoCount(0)               );
+    }
+
+    #[test]
+    fn it_rewrites_fields_in_place() {
+        let mut message = query();
+        let mut header = RawHeader::try_from(&mut message[..]).unwrap();
+        header.set_id(id::Id(0x1234));
+        header.set_qr(one_bit_flag::Qr::Response);
+        header.set_ra(one_bit_flag::Ra::RecursionAvailable);
+        header.set_rcode(rcode::Rcode::NameError).unwrap();
+        assert_eq!(header.set_rcode(rcode::Rcode::Badcookie), Err(error::value_too_large(Field::Rcode, 3, 23)));
+        header.set_arcount(count::Arcount(1));
+
+        let decoded = Header::try_from(&message[..]).unwrap();
+        assert_eq!(decoded, Header {
+            qr:      one_bit_flag::Qr::Response,
+            rd:      one_bit_flag::Rd::RecursiveDesired,
+            ra:      one_bit_flag::Ra::RecursionAvailable,
+            rcode:   rcode::Rcode::NameError,
+            arcount: count::Arcount(1),
+            ..Header::query(id::Id(0x1234), opcode::Opcode::Query)
+        });
+    }
+
+    #[test]
+    fn it_reports_error_when_header_lack() {
+        let message = [0; 5];
+        assert_eq!(
+            RawHeader::try_from(&message[..]),
+            Err(error::truncated(Field::QdZoCount, 4)),
+        );
+    }
+}
@@ -1,3 +1,6 @@
+use crate::error::UnknownMnemonic;
+use crate::presentation;
+
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Rcode {
@@ -23,12 +26,62 @@ pub enum Rcode {
     Badcookie,
     Private(u16),
     Reserved,
+    /// Unassigned value kept by lenient decoding.
+    Unknown(u16),
 }
 
 impl Rcode {
-    pub fn to_hex_string(&self) -> String {
+    #[cfg(feature = "alloc")]
+    pub fn to_hex_string(&self) -> alloc::string::String {
         let value: u16 = self.clone().into();
-        format!("{:04X}", value)
+        alloc::format!("{:04X}", value)
+    }
+}
+
+/// IANA mnemonics, as printed by dig. 16 is BADVERS with an OPT RR and BADSIG in TSIG and
+/// TKEY errors (RFC 6895 §2.3); both parse, and BADVERS is printed.
+const MNEMONICS: [(&str, Rcode); 21] = [
+    ("NOERROR",   Rcode::NoError        ),
+    ("FORMERR",   Rcode::FormatError    ),
+    ("SERVFAIL",  Rcode::ServerFailure  ),
+    ("NXDOMAIN",  Rcode::NameError      ),
+    ("NOTIMP",    Rcode::NotImplemented ),
+    ("REFUSED",   Rcode::Refused        ),
+    ("YXDOMAIN",  Rcode::YxDomain       ),
+    ("YXRRSET",   Rcode::YxRrset        ),
+    ("NXRRSET",   Rcode::NxRrset        ),
+    ("NOTAUTH",   Rcode::NotAuth        ),
+    ("NOTZONE",   Rcode::NotZone        ),
+    ("DSOTYPENI", Rcode::Dsotypeni      ),
+    ("BADVERS",   Rcode::BadversOrBadsig),
+    ("BADSIG",    Rcode::BadversOrBadsig),
+    ("BADKEY",    Rcode::Badkey         ),
+    ("BADTIME",   Rcode::Badtime        ),
+    ("BADMODE",   Rcode::Badmode        ),
+    ("BADNAME",   Rcode::Badname        ),
+    ("BADALG",    Rcode::Badalg         ),
+    ("BADTRUNC",  Rcode::Badtrunc       ),
+    ("BADCOOKIE", Rcode::Badcookie      ),
+];
+
+/// Mnemonic, or the value in decimal for rcodes without one.
+impl core::fmt::Display for Rcode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let value: u16 = (*self).into();
+        presentation::fmt_mnemonic(presentation::mnemonic_of(&MNEMONICS, self), value, f)
+    }
+}
+
+/// Accepts the mnemonics in any case, and values in decimal.
+impl core::str::FromStr for Rcode {
+    type Err = UnknownMnemonic;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(rcode) = presentation::from_mnemonic(&MNEMONICS, s) {
+            return Ok(rcode);
+        }
+        let value = s.parse::<u16>().map_err(|_| UnknownMnemonic { kind: "RCODE" })?;
+        Ok(Self::try_from(value).unwrap_or(Self::Unknown(value)))
     }
 }
 
@@ -38,7 +91,7 @@ pub enum FromError {
     LogicError(u16),
 }
 
-impl std::convert::TryFrom<u16> for Rcode {
+impl core::convert::TryFrom<u16> for Rcode {
     type Error = FromError;
     fn try_from(value: u16) -> Result<Self, Self::Error> {
         if (24 <= value && value <= 3840) || (4096 <= value && value <= 65534) {
@@ -74,7 +127,7 @@ impl std::convert::TryFrom<u16> for Rcode {
     }
 }
 
-impl std::convert::Into<u16> for Rcode {
+impl core::convert::Into<u16> for Rcode {
     fn into(self) -> u16 {
         match self {
             Self::NoError         => 0,
@@ -99,6 +152,7 @@ impl std::convert::Into<u16> for Rcode {
             Self::Badcookie       => 23,
             Self::Private(v) => v,
             Self::Reserved        => 65535,
+            Self::Unknown(v)      => v,
         }
     }
 }
@@ -154,6 +208,8 @@ mod tests {
         assert_eq!(value, 3841);
         let value: u16 = Rcode::Reserved.into();
         assert_eq!(value, 65535);
+        let value: u16 = Rcode::Unknown(12).into();
+        assert_eq!(value, 12);
     }
 
     #[test]
@@ -193,6 +249,20 @@ mod tests {
         assert_eq!(Rcode::try_from(65534_u16), Err(FromError::Unassigned(65534_u16)));
     }
 
+    #[test]
+    fn it_parses_and_displays_mnemonic() {
+        assert_eq!("NXDOMAIN".parse(),           Ok(Rcode::NameError)                  );
+        assert_eq!("badcookie".parse(),          Ok(Rcode::Badcookie)                  );
+        assert_eq!("BADSIG".parse(),             Ok(Rcode::BadversOrBadsig)            );
+        assert_eq!("3841".parse(),               Ok(Rcode::Private(3841))              );
+        assert_eq!("12".parse(),                 Ok(Rcode::Unknown(12))                );
+        assert_eq!("NXDOMAINS".parse::<Rcode>(), Err(UnknownMnemonic { kind: "RCODE" }));
+        assert_eq!(Rcode::ServerFailure.to_string(),   "SERVFAIL");
+        assert_eq!(Rcode::BadversOrBadsig.to_string(), "BADVERS" );
+        assert_eq!(Rcode::Reserved.to_string(),        "65535"   );
+    }
+
+    #[cfg(feature = "alloc")]
     #[test]
     fn it_converts_to_hex_string() {
         assert_eq!(Rcode::NoError.to_hex_string(),         "0000");
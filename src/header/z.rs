@@ -1,25 +1,35 @@
+#[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Z;
+pub enum Z {
+    Zero,
+    /// Nonzero bit kept by lenient decoding.
+    Unknown(u8),
+}
 
 impl Z {
-    pub fn to_hex_string(&self) -> String {
-        "0".to_string()
+    #[cfg(feature = "alloc")]
+    pub fn to_hex_string(&self) -> alloc::string::String {
+        let value: u8 = (*self).into();
+        alloc::format!("{:X}", value)
     }
 }
 
-impl std::convert::TryFrom<u8> for Z {
+impl core::convert::TryFrom<u8> for Z {
     type Error = u8;
     fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0 => Ok(Z),
+            0 => Ok(Z::Zero),
             v => Err(v),
         }
     }
 }
 
-impl std::convert::Into<u8> for Z {
+impl core::convert::Into<u8> for Z {
     fn into(self) -> u8 {
-        0
+        match self {
+            Self::Zero       => 0,
+            Self::Unknown(v) => v,
+        }
     }
 }
 
@@ -30,13 +40,15 @@ mod tests {
 
     #[test]
     fn it_converts_to_raw_value() {
-        let value: u8 = Z.into();
+        let value: u8 = Z::Zero.into();
         assert_eq!(value, 0);
+        let value: u8 = Z::Unknown(1).into();
+        assert_eq!(value, 1);
     }
 
     #[test]
     fn it_converts_from_u8() {
-        assert_eq!(Z::try_from(0_u8), Ok(Z));
+        assert_eq!(Z::try_from(0_u8), Ok(Z::Zero));
     }
 
     #[test]
@@ -44,8 +56,10 @@ mod tests {
         assert_eq!(Z::try_from(1_u8), Err(1_u8));
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn it_converts_to_hex_string() {
-        assert_eq!(Z.to_hex_string(), "0");
+        assert_eq!(Z::Zero.to_hex_string(), "0");
+        assert_eq!(Z::Unknown(1).to_hex_string(), "1");
     }
 }
@@ -1,4 +1,43 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "tokio")]
+pub mod codec;
+#[cfg(feature = "alloc")]
+pub mod dso;
+pub mod error;
 pub mod header;
+#[cfg(feature = "alloc")]
+pub mod message;
+#[cfg(feature = "alloc")]
+pub mod name;
+#[cfg(feature = "alloc")]
+pub mod notify;
+#[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+mod presentation;
+#[cfg(feature = "alloc")]
+pub mod question;
+#[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+mod reader;
+#[cfg(feature = "alloc")]
+pub mod record;
+pub mod sink;
+pub mod tcp;
+#[cfg(feature = "alloc")]
+pub mod sig0;
+#[cfg(feature = "alloc")]
+pub mod tkey;
+#[cfg(feature = "alloc")]
+pub mod tsig;
+pub mod typed;
+#[cfg(feature = "alloc")]
+pub mod udp;
+#[cfg(feature = "alloc")]
+pub mod update;
+#[cfg(feature = "alloc")]
+pub mod zone;
 
 pub fn add(left: usize, right: usize) -> usize {
     left + right
@@ -0,0 +1,489 @@
+use alloc::{vec, vec::Vec};
+use crate::reader::Reader;
+use crate::dso::DsoMessage;
+use crate::error::{self, DecodeError, EncodeError, Field, Section};
+use crate::header::{count, opcode, rcode, Header};
+use crate::question::{Question, QuestionRef};
+use crate::record::{rtype, Record, RecordRef};
+use crate::sink::{self, Sink};
+use crate::update::UpdateMessage;
+
+pub mod builder;
+
+const HEADER_LENGTH: usize = 12;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(test, derive(PartialEq, Eq))]
+pub struct Message {
+    pub header:      Header,
+    pub questions:   Vec<Question>,
+    pub answers:     Vec<Record>,
+    pub authorities: Vec<Record>,
+    pub additionals: Vec<Record>,
+}
+
+/// A decoded message, in the section layout its opcode calls for.
+#[derive(Debug, Clone)]
+#[cfg_attr(test, derive(PartialEq, Eq))]
+pub enum Decoded {
+    Standard(Message),
+    Update(UpdateMessage),
+    Dso(DsoMessage),
+}
+
+/// A message borrowed from its wire form. Construction checks the framing of every question
+/// and record once; fields are then decoded while iterating, and RDATA only on request.
+#[derive(Debug, Clone, Copy)]
+pub struct MessageRef<'a> {
+    buffer:   &'a [u8],
+    header:   Header,
+    /// Offsets where the question, answer, authority and additional sections start.
+    sections: [usize; 4],
+}
+
+/// Questions of a [`MessageRef`].
+#[derive(Debug, Clone)]
+pub struct Questions<'a> {
+    buffer:    &'a [u8],
+    offset:    usize,
+    remaining: u16,
+}
+
+/// Records of one section of a [`MessageRef`].
+#[derive(Debug, Clone)]
+pub struct Records<'a> {
+    buffer:    &'a [u8],
+    offset:    usize,
+    remaining: u16,
+    section:   Section,
+}
+
+fn read_records(buffer: &mut Reader, count: u16, section: Section) -> Result<Vec<Record>, DecodeError> {
+    (0..count).map(|_| Record::read_from(buffer).map_err(|e| e.in_section(section))).collect()
+}
+
+fn count<Count>(records: usize, field: Field, start: usize, wrap: fn(u16) -> Count) -> Result<Count, EncodeError> {
+    let offset = start + match field {
+        Field::QdZoCount => 4,
+        Field::AnPrCount => 6,
+        Field::NsUpCount => 8,
+        _                => 10,
+    };
+    u16::try_from(records)
+        .map(wrap)
+        .map_err(|_| error::too_long(field, offset, records))
+}
+
+impl core::convert::TryFrom<&[u8]> for Message {
+    type Error = DecodeError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let header = Header::try_from(value)?;
+        let mut buffer = Reader::new(value);
+        buffer.set_position(HEADER_LENGTH);
+        let questions = (0..header.qd_zo_count.into())
+            .map(|_| Question::read_from(&mut buffer).map_err(|e| e.in_section(Section::Question)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let answers = read_records(&mut buffer, header.an_pr_count.into(), Section::Answer)?;
+        let authorities = read_records(&mut buffer, header.ns_up_count.into(), Section::Authority)?;
+        let additionals = read_records(&mut buffer, header.arcount.into(), Section::Additional)?;
+        if buffer.position() != value.len() {
+            return Err(DecodeError::TrailingBytes { section: Section::Additional, offset: buffer.position() });
+        }
+
+        Ok(Self { header, questions, answers, authorities, additionals })
+    }
+}
+
+impl core::convert::TryFrom<&[u8]> for Decoded {
+    type Error = DecodeError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        match Header::try_from(value)?.opcode {
+            opcode::Opcode::Update                => Ok(Self::Update(UpdateMessage::try_from(Message::try_from(value)?)?)),
+            opcode::Opcode::DnsStatefulOperations => Ok(Self::Dso(DsoMessage::try_from(value)?)),
+            _                                     => Ok(Self::Standard(Message::try_from(value)?)),
+        }
+    }
+}
+
+impl<'a> core::convert::TryFrom<&'a [u8]> for MessageRef<'a> {
+    type Error = DecodeError;
+
+    fn try_from(value: &'a [u8]) -> Result<Self, Self::Error> {
+        let header = Header::try_from(value)?;
+        let mut offset = HEADER_LENGTH;
+        let mut sections = [offset; 4];
+        for _ in 0..header.qd_zo_count.into() {
+            offset = QuestionRef::validate(value, offset).map_err(|e| e.in_section(Section::Question))?;
+        }
+        let counts = [
+            (header.an_pr_count.into(), Section::Answer),
+            (header.ns_up_count.into(), Section::Authority),
+            (header.arcount.into(),     Section::Additional),
+        ];
+        for (i, (count, section)) in counts.into_iter().enumerate() {
+            sections[i + 1] = offset;
+            for _ in 0..count {
+                offset = RecordRef::validate(value, offset).map_err(|e| e.in_section(section))?;
+            }
+        }
+        if offset != value.len() {
+            return Err(DecodeError::TrailingBytes { section: Section::Additional, offset });
+        }
+
+        Ok(Self { buffer: value, header, sections })
+    }
+}
+
+impl<'a> MessageRef<'a> {
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    pub fn questions(&self) -> Questions<'a> {
+        Questions { buffer: self.buffer, offset: self.sections[0], remaining: self.header.qd_zo_count.into() }
+    }
+
+    pub fn answers(&self) -> Records<'a> {
+        self.records(1, self.header.an_pr_count.into(), Section::Answer)
+    }
+
+    pub fn authorities(&self) -> Records<'a> {
+        self.records(2, self.header.ns_up_count.into(), Section::Authority)
+    }
+
+    pub fn additionals(&self) -> Records<'a> {
+        self.records(3, self.header.arcount.into(), Section::Additional)
+    }
+
+    fn records(&self, index: usize, count: u16, section: Section) -> Records<'a> {
+        Records { buffer: self.buffer, offset: self.sections[index], remaining: count, section }
+    }
+
+    /// Decodes the whole message, including every RDATA.
+    pub fn to_message(&self) -> Result<Message, DecodeError> {
+        Ok(Message {
+            header:      self.header,
+            questions:   self.questions().map(|question| question.to_question()).collect(),
+            answers:     self.answers().map(|record| record.to_record()).collect::<Result<_, _>>()?,
+            authorities: self.authorities().map(|record| record.to_record()).collect::<Result<_, _>>()?,
+            additionals: self.additionals().map(|record| record.to_record()).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl<'a> Iterator for Questions<'a> {
+    type Item = QuestionRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let (question, end) = QuestionRef::read_validated(self.buffer, self.offset);
+        self.offset = end;
+        self.remaining -= 1;
+        Some(question)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining as usize, Some(self.remaining as usize))
+    }
+}
+
+impl ExactSizeIterator for Questions<'_> {}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = RecordRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let (record, end) = RecordRef::read_validated(self.buffer, self.offset, self.section);
+        self.offset = end;
+        self.remaining -= 1;
+        Some(record)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining as usize, Some(self.remaining as usize))
+    }
+}
+
+impl ExactSizeIterator for Records<'_> {}
+
+impl Message {
+    /// Encodes into `sink` with the counts taken from the sections, returning the number of bytes written.
+    pub fn encode_to(&self, sink: &mut impl Sink) -> Result<usize, EncodeError> {
+        let start = sink.written();
+        let header = Header {
+            qd_zo_count: count(self.questions.len(),   Field::QdZoCount, start, count::QdZoCount)?,
+            an_pr_count: count(self.answers.len(),     Field::AnPrCount, start, count::AnPrCount)?,
+            ns_up_count: count(self.authorities.len(), Field::NsUpCount, start, count::NsUpCount)?,
+            arcount:     count(self.additionals.len(), Field::Arcount,   start, count::Arcount)?,
+            ..self.header
+        };
+        header.write_to(sink)?;
+        for question in &self.questions {
+            question.write_to(sink);
+        }
+        let sections = [
+            (&self.answers,     Section::Answer),
+            (&self.authorities, Section::Authority),
+            (&self.additionals, Section::Additional),
+        ];
+        for (records, section) in sections {
+            for record in records {
+                record.write_to(sink).map_err(|e| e.in_section(section))?;
+            }
+        }
+
+        Ok(sink.written() - start)
+    }
+
+    /// Encodes into `buffer`, returning the number of bytes written.
+    pub fn encode_into(&self, buffer: &mut [u8]) -> Result<usize, EncodeError> {
+        sink::encode_into(buffer, |sink| self.encode_to(sink))
+    }
+}
+
+impl core::convert::TryInto<Vec<u8>> for Message {
+    type Error = EncodeError;
+
+    fn try_into(self) -> Result<Vec<u8>, Self::Error> {
+        let mut buffer = vec![];
+        self.encode_to(&mut buffer)?;
+
+        Ok(buffer)
+    }
+}
+
+/// Presentation as dig prints a message: the header lines, the EDNS pseudosection, then every
+/// non-empty section in zone-file syntax. The extended RCODE of an OPT RR is folded into the status.
+impl core::fmt::Display for Message {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let saturated = |records: usize| u16::try_from(records).unwrap_or(u16::MAX);
+        let mut header = Header {
+            qd_zo_count: count::QdZoCount(saturated(self.questions.len())),
+            an_pr_count: count::AnPrCount(saturated(self.answers.len())),
+            ns_up_count: count::NsUpCount(saturated(self.authorities.len())),
+            arcount:     count::Arcount(saturated(self.additionals.len())),
+            ..self.header
+        };
+        let opt = self.additionals.iter().find(|record| record.rtype == rtype::Type::Opt);
+        if let Some(opt) = opt {
+            let value = ((opt.ttl >> 24) as u16) << 4 | Into::<u16>::into(header.rcode);
+            header.rcode = rcode::Rcode::try_from(value).unwrap_or(rcode::Rcode::Unknown(value));
+        }
+        write!(f, "{}", header)?;
+
+        if let Some(opt) = opt {
+            write!(f, "\n\n;; OPT PSEUDOSECTION:\n; EDNS: version: {}, flags:", (opt.ttl >> 16) as u8)?;
+            if opt.ttl & 0x8000 != 0 {
+                f.write_str(" do")?;
+            }
+            write!(f, "; udp: {}", u16::from(opt.class))?;
+        }
+        let titles = match header.opcode {
+            opcode::Opcode::Update => ["ZONE", "PREREQUISITE", "UPDATE", "ADDITIONAL"],
+            _                      => ["QUESTION", "ANSWER", "AUTHORITY", "ADDITIONAL"],
+        };
+        if !self.questions.is_empty() {
+            write!(f, "\n\n;; {} SECTION:", titles[0])?;
+            for question in &self.questions {
+                write!(f, "\n;{}\t\t{}\t{}", question.name, question.qclass, question.qtype)?;
+            }
+        }
+        let sections = [(titles[1], &self.answers), (titles[2], &self.authorities), (titles[3], &self.additionals)];
+        for (title, records) in sections {
+            let mut records = records.iter().filter(|record| record.rtype != rtype::Type::Opt).peekable();
+            if records.peek().is_none() {
+                continue;
+            }
+            write!(f, "\n\n;; {} SECTION:", title)?;
+            for record in records {
+                write!(f, "\n{}", record)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::{id, one_bit_flag};
+    use crate::record::{class, rdata};
+    use pretty_assertions::assert_eq;
+
+    fn query_buffer() -> Vec<u8> {
+        vec![
+            0xAB, 0xCD,                   // ID = 0xABCD
+            0x01, 0x00,                   // RD = Recursion desired, other flags cleared
+            0x00, 0x01,                   // QDCOUNT = 1
+            0x00, 0x01,                   // ANCOUNT = 1
+            0x00, 0x00,                   // NSCOUNT = 0
+            0x00, 0x00,                   // ARCOUNT = 0
+            0x03, b'w', b'w', b'w', 0x00, // QNAME   = www.
+            0x00, 0x01,                   // QTYPE   = A
+            0x00, 0x01,                   // QCLASS  = IN
+            0xC0, 0x0C,                   // NAME    = pointer to QNAME
+            0x00, 0x01,                   // TYPE    = A
+            0x00, 0x01,                   // CLASS   = IN
+            0x00, 0x00, 0x00, 0x3C,       // TTL     = 60
+            0x00, 0x04,                   // RDLENGTH
+            192, 0, 2, 1,                 // RDATA   = 192.0.2.1
+        ]
+    }
+
+    #[test]
+    fn it_decodes_from_u8_slice() {
+        let message = Message::try_from(query_buffer().as_slice()).unwrap();
+        assert_eq!(message.header.id, id::Id(0xABCD));
+        assert_eq!(message.header.rd, one_bit_flag::Rd::RecursiveDesired);
+        assert_eq!(message.questions, vec![Question {
+            name:   "www.".parse().unwrap(),
+            qtype:  rtype::Type::A,
+            qclass: class::Class::In,
+        }]);
+        assert_eq!(message.answers, vec![Record {
+            name:  "www.".parse().unwrap(),
+            rtype: rtype::Type::A,
+            class: class::Class::In,
+            ttl:   60,
+            rdata: rdata::RData::A(core::net::Ipv4Addr::new(192, 0, 2, 1)),
+        }]);
+        assert!(message.authorities.is_empty());
+        assert!(message.additionals.is_empty());
+    }
+
+    #[test]
+    fn it_reports_error_when_section_lack() {
+        let mut buffer = query_buffer();
+        buffer[11] = 0x01; // ARCOUNT = 1 (!!!LACK!!! no additional record follows)
+        let error = Message::try_from(buffer.as_slice()).unwrap_err();
+        assert_eq!(error, DecodeError::Truncated { section: Section::Additional, field: Field::Label, offset: 37 }, "should not decode lack section");
+
+        let mut buffer = query_buffer();
+        buffer.push(0x00);
+        let error = Message::try_from(buffer.as_slice()).unwrap_err();
+        assert_eq!(error, DecodeError::TrailingBytes { section: Section::Additional, offset: 37 }, "should not decode trailing bytes");
+    }
+
+    #[test]
+    fn it_encodes_with_counts_from_sections() {
+        let message = Message::try_from(query_buffer().as_slice()).unwrap();
+        let message = Message {
+            header: Header { an_pr_count: count::AnPrCount(0xFFFF), ..message.header },
+            ..message
+        };
+        let encoded: Vec<u8> = message.clone().try_into().unwrap();
+        assert_eq!(&encoded[4..12], &[0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00]);
+        assert_eq!(Message::try_from(encoded.as_slice()).unwrap().answers, message.answers);
+    }
+
+    #[test]
+    fn it_views_without_decoding() {
+        let buffer = query_buffer();
+        let view = MessageRef::try_from(buffer.as_slice()).unwrap();
+        assert_eq!(view.header().id, id::Id(0xABCD));
+        let questions: Vec<_> = view.questions().collect();
+        assert_eq!(questions.len(), 1);
+        assert_eq!(questions[0].qtype, rtype::Type::A);
+        let answer = view.answers().next().unwrap();
+        assert_eq!(answer.name, "www.".parse::<crate::name::Name>().unwrap());
+        assert_eq!(answer.ttl, 60);
+        assert_eq!(answer.rdata, &[192, 0, 2, 1]);
+        assert_eq!(view.authorities().len(), 0);
+        assert_eq!(view.additionals().len(), 0);
+        assert_eq!(view.to_message(), Message::try_from(buffer.as_slice()));
+    }
+
+    #[test]
+    fn it_reports_error_when_view_framing_broken() {
+        let mut buffer = query_buffer();
+        buffer[11] = 0x01; // ARCOUNT = 1 (!!!LACK!!! no additional record follows)
+        let error = MessageRef::try_from(buffer.as_slice()).unwrap_err();
+        assert_eq!(error, DecodeError::Truncated { section: Section::Additional, field: Field::Label, offset: 37 });
+
+        let mut buffer = query_buffer();
+        buffer[32] = 0x05; // RDLENGTH = 5 (!!!LACK!!! only 4 octets follow)
+        let error = MessageRef::try_from(buffer.as_slice()).unwrap_err();
+        assert_eq!(error, DecodeError::Truncated { section: Section::Answer, field: Field::RData(rtype::Type::A), offset: 33 });
+
+        let mut buffer = query_buffer();
+        buffer[32] = 0x05; // RDLENGTH = 5 (!!!TOO LONG!!! for A RDATA)
+        buffer.push(0x00);
+        let view = MessageRef::try_from(buffer.as_slice()).unwrap();
+        assert_eq!(view.answers().next().unwrap().to_record(), Err(DecodeError::LengthMismatch {
+            section: Section::Answer,
+            field:   Field::RdLength,
+            offset:  33,
+            length:  5,
+        }), "should check RDATA only when decoded");
+    }
+
+    #[test]
+    fn it_encodes_into_u8_slice() {
+        let message = Message::try_from(query_buffer().as_slice()).unwrap();
+        let encoded: Vec<u8> = message.clone().try_into().unwrap();
+        let mut buffer = [0; 512];
+        assert_eq!(message.encode_into(&mut buffer), Ok(40));
+        assert_eq!(&buffer[..40], encoded.as_slice());
+
+        let mut buffer = [0; 30];
+        assert_eq!(message.encode_into(&mut buffer), Err(EncodeError::BufferTooSmall { needed: 10 }));
+    }
+
+    #[test]
+    fn it_decodes_by_opcode() {
+        let buffer = query_buffer();
+        assert!(matches!(Decoded::try_from(buffer.as_slice()), Ok(Decoded::Standard(_))));
+
+        let mut buffer = query_buffer();
+        buffer.truncate(21);                          // keep the question only
+        buffer[2] |= 0b0010_1000;                     // OPCODE  = Update
+        buffer[6..8].copy_from_slice(&[0x00, 0x00]);  // PRCOUNT = 0
+        buffer[18] = 0x06;                            // ZTYPE   = SOA
+        assert!(matches!(Decoded::try_from(buffer.as_slice()), Ok(Decoded::Update(_))));
+
+        let mut buffer = query_buffer();
+        buffer.truncate(12);                          // keep the header only
+        buffer[2] |= 0b0011_0000;                     // OPCODE  = DSO
+        buffer[4..8].copy_from_slice(&[0x00; 4]);     // QDCOUNT = ANCOUNT = 0
+        assert!(matches!(Decoded::try_from(buffer.as_slice()), Ok(Decoded::Dso(_))));
+    }
+
+    #[test]
+    fn it_displays_like_dig() {
+        let query = Message {
+            additionals: vec![Record {
+                name:  crate::name::Name::root(),
+                rtype: rtype::Type::Opt,
+                class: class::Class::from(1232),
+                ttl:   0x0000_8000,
+                rdata: rdata::RData::Empty,
+            }],
+            ..Message::try_from(query_buffer().as_slice()).unwrap()
+        };
+        let response = builder::ResponseBuilder::new(&query)
+            .rcode(rcode::Rcode::Badcookie)
+            .answer(query.answers[0].clone())
+            .build();
+        assert_eq!(
+            response.to_string(),
+            ";; ->>HEADER<<- opcode: QUERY, status: BADCOOKIE, id: 43981\n\
+             ;; flags: qr rd; QUERY: 1, ANSWER: 1, AUTHORITY: 0, ADDITIONAL: 1\n\
+             \n\
+             ;; OPT PSEUDOSECTION:\n\
+             ; EDNS: version: 0, flags: do; udp: 1232\n\
+             \n\
+             ;; QUESTION SECTION:\n\
+             ;www.\t\tIN\tA\n\
+             \n\
+             ;; ANSWER SECTION:\n\
+             www.\t60\tIN\tA\t192.0.2.1",
+        );
+    }
+}
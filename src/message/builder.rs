@@ -0,0 +1,158 @@
+use alloc::{vec, vec::Vec};
+use crate::header::{one_bit_flag, rcode, Header};
+use crate::name::Name;
+use crate::question::Question;
+use crate::record::rdata::RData;
+use crate::record::{rtype, Record};
+use super::Message;
+
+/// DO bit in the TTL of an OPT RR (RFC 3225 §3).
+const DNSSEC_OK: u32 = 0x0000_8000;
+
+/// Starts a response to a query: copies ID, OPCODE, RD and CD, sets QR, and echoes the
+/// question section and EDNS. AA, RA, RCODE and the records are up to the caller.
+pub struct ResponseBuilder {
+    header:      Header,
+    questions:   Vec<Question>,
+    answers:     Vec<Record>,
+    authorities: Vec<Record>,
+    additionals: Vec<Record>,
+    opt:         Option<Record>,
+}
+
+impl ResponseBuilder {
+    pub fn new(query: &Message) -> Self {
+        let header = Header::response(&query.header);
+        // the requester's payload size and DO bit, without its options (RFC 6891 §7)
+        let opt = query.additionals.iter()
+            .find(|record| record.rtype == rtype::Type::Opt)
+            .map(|opt| Record {
+                name:  Name::root(),
+                rtype: rtype::Type::Opt,
+                class: opt.class,
+                ttl:   opt.ttl & DNSSEC_OK,
+                rdata: RData::Empty,
+            });
+        Self {
+            header,
+            questions:   query.questions.clone(),
+            answers:     vec![],
+            authorities: vec![],
+            additionals: vec![],
+            opt,
+        }
+    }
+
+    pub fn aa(mut self, aa: one_bit_flag::Aa) -> Self {
+        self.header.aa = aa;
+        self
+    }
+
+    pub fn ra(mut self, ra: one_bit_flag::Ra) -> Self {
+        self.header.ra = ra;
+        self
+    }
+
+    /// Sets RCODE. Values above 15 are split into the OPT RR when the query had one (RFC 6891 §6.1.3).
+    pub fn rcode(mut self, rcode: rcode::Rcode) -> Self {
+        self.header.rcode = rcode;
+        self
+    }
+
+    pub fn answer(mut self, record: Record) -> Self {
+        self.answers.push(record);
+        self
+    }
+
+    pub fn authority(mut self, record: Record) -> Self {
+        self.authorities.push(record);
+        self
+    }
+
+    pub fn additional(mut self, record: Record) -> Self {
+        self.additionals.push(record);
+        self
+    }
+
+    /// Builds the response, with the OPT RR after the other additional records.
+    pub fn build(self) -> Message {
+        let mut header = self.header;
+        let mut additionals = self.additionals;
+        if let Some(mut opt) = self.opt {
+            let rcode: u16 = header.rcode.into();
+            if rcode > 0x0F {
+                let low = rcode & 0x0F;
+                header.rcode = rcode::Rcode::try_from(low).unwrap_or(rcode::Rcode::Unknown(low));
+                opt.ttl |= ((rcode >> 4) as u32 & 0xFF) << 24;
+            }
+            additionals.push(opt);
+        }
+        Message {
+            header,
+            questions:   self.questions,
+            answers:     self.answers,
+            authorities: self.authorities,
+            additionals,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::{id, opcode};
+    use crate::record::class;
+    use pretty_assertions::assert_eq;
+
+    fn query() -> Message {
+        Message {
+            header:      Header {
+                rd: one_bit_flag::Rd::RecursiveDesired,
+                cd: one_bit_flag::Cd::DnssecForbidden,
+                ..Header::query(id::Id(0x1234), opcode::Opcode::Query)
+            },
+            questions:   vec![Question { name: "www.example.".parse().unwrap(), qtype: rtype::Type::A, qclass: class::Class::In }],
+            answers:     vec![],
+            authorities: vec![],
+            additionals: vec![Record {
+                name:  Name::root(),
+                rtype: rtype::Type::Opt,
+                class: class::Class::from(1232),
+                ttl:   0x0000_8000,          // DO
+                rdata: RData::Unknown(vec![0x00, 0x0A, 0x00, 0x00]), // empty COOKIE option
+            }],
+        }
+    }
+
+    #[test]
+    fn it_builds_response_from_query() {
+        let response = ResponseBuilder::new(&query())
+            .aa(one_bit_flag::Aa::FromAuthority)
+            .build();
+        assert_eq!(response.header.id, id::Id(0x1234));
+        assert_eq!(response.header.qr, one_bit_flag::Qr::Response);
+        assert_eq!(response.header.aa, one_bit_flag::Aa::FromAuthority);
+        assert_eq!(response.header.rd, one_bit_flag::Rd::RecursiveDesired);
+        assert_eq!(response.header.cd, one_bit_flag::Cd::DnssecForbidden);
+        assert_eq!(response.header.ra, one_bit_flag::Ra::RecursionUnavailable);
+        assert_eq!(response.questions, query().questions);
+        assert_eq!(response.additionals, vec![Record {
+            name:  Name::root(),
+            rtype: rtype::Type::Opt,
+            class: class::Class::from(1232),
+            ttl:   0x0000_8000,
+            rdata: RData::Empty,
+        }]);
+    }
+
+    #[test]
+    fn it_splits_extended_rcode_into_opt() {
+        let response = ResponseBuilder::new(&query())
+            .rcode(rcode::Rcode::Badcookie)
+            .build();
+        assert_eq!(response.header.rcode, rcode::Rcode::YxRrset);
+        assert_eq!(response.additionals[0].ttl, 0x0100_8000);
+        let encoded: Result<Vec<u8>, _> = response.try_into();
+        assert!(encoded.is_ok());
+    }
+}
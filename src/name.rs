@@ -0,0 +1,433 @@
+use alloc::{vec, vec::Vec};
+use crate::reader::Reader;
+use crate::error::{self, DecodeError, Field};
+use crate::sink::Sink;
+
+const MAX_LABEL_LENGTH: usize = 63;
+const MAX_NAME_LENGTH: usize = 255;
+const POINTER_MASK: u8 = 0b1100_0000;
+
+#[derive(Debug, Clone, Default)]
+pub struct Name {
+    labels: Vec<Vec<u8>>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FromError {
+    EmptyLabel,
+    LabelTooLong(usize),
+    NameTooLong(usize),
+    InvalidEscape,
+}
+
+impl core::fmt::Display for FromError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::EmptyLabel             => write!(f, "found empty label"),
+            Self::LabelTooLong(length)   => write!(f, "label is too long: {} octets", length),
+            Self::NameTooLong(length)    => write!(f, "name is too long: {} octets", length),
+            Self::InvalidEscape          => write!(f, "found invalid escape sequence"),
+        }
+    }
+}
+
+impl core::error::Error for FromError {}
+
+impl Name {
+    pub fn root() -> Self {
+        Self { labels: vec![] }
+    }
+
+    pub fn from_labels<Labels, Label>(labels: Labels) -> Result<Self, FromError>
+        where Labels: IntoIterator<Item = Label>,
+              Label: Into<Vec<u8>>
+    {
+        let labels: Vec<Vec<u8>> = labels.into_iter().map(Into::into).collect();
+        for label in &labels {
+            if label.is_empty() {
+                return Err(FromError::EmptyLabel);
+            }
+            if label.len() > MAX_LABEL_LENGTH {
+                return Err(FromError::LabelTooLong(label.len()));
+            }
+        }
+        let name = Self { labels };
+        if name.wire_length() > MAX_NAME_LENGTH {
+            return Err(FromError::NameTooLong(name.wire_length()));
+        }
+        Ok(name)
+    }
+
+    pub fn labels(&self) -> &[Vec<u8>] {
+        &self.labels
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Length of the uncompressed wire form, including the terminating root label.
+    pub fn wire_length(&self) -> usize {
+        self.labels.iter().map(|label| label.len() + 1).sum::<usize>() + 1
+    }
+
+    /// Whether `self` equals `other` or lies below it.
+    pub fn is_subdomain_of(&self, other: &Name) -> bool {
+        if self.labels.len() < other.labels.len() {
+            return false;
+        }
+        self.labels.iter().rev()
+            .zip(other.labels.iter().rev())
+            .all(|(left, right)| left.eq_ignore_ascii_case(right))
+    }
+
+    /// Lowercased copy, as used in canonical forms for signing.
+    pub fn to_lowercase(&self) -> Name {
+        Self { labels: self.labels.iter().map(|label| label.to_ascii_lowercase()).collect() }
+    }
+
+    pub fn parent(&self) -> Option<Name> {
+        if self.is_root() {
+            None
+        } else {
+            Some(Self { labels: self.labels[1..].to_vec() })
+        }
+    }
+
+    pub(crate) fn read_from(buffer: &mut Reader) -> Result<Self, DecodeError> {
+        let mut labels = vec![];
+        let end = walk_labels(buffer.get_ref(), buffer.position(), |label| labels.push(label.to_vec()))?;
+        buffer.set_position(end);
+
+        Ok(Self { labels })
+    }
+
+    pub(crate) fn write_to(&self, buffer: &mut impl Sink) {
+        for label in &self.labels {
+            buffer.put_u8(label.len() as u8);
+            buffer.put_slice(label);
+        }
+        buffer.put_u8(0);
+    }
+}
+
+/// Visits the labels of the name at `start` in `message`, following compression pointers,
+/// and returns the offset just past the name where it starts.
+fn walk_labels<'a>(message: &'a [u8], start: usize, mut visit: impl FnMut(&'a [u8])) -> Result<usize, DecodeError> {
+    let mut buffer = Reader::new(message);
+    buffer.set_position(start);
+    let mut wire_length = 1;
+    // position to restore once the first compression pointer has been followed
+    let mut resume_at = None;
+    let mut lowest_pointer = start;
+
+    loop {
+        let offset = buffer.position();
+        let length = buffer.read_u8_field(Field::Label)?;
+        match length & POINTER_MASK {
+            0b0000_0000 => {
+                if length == 0 {
+                    break;
+                }
+                let label_at = buffer.position();
+                let label = message.get(label_at..label_at + length as usize)
+                    .ok_or_else(|| error::truncated(Field::Label, label_at))?;
+                wire_length += label.len() + 1;
+                if wire_length > MAX_NAME_LENGTH {
+                    return Err(error::invalid_value(Field::Name, start, wire_length as u32));
+                }
+                visit(label);
+                buffer.set_position(label_at + label.len());
+            }
+            POINTER_MASK => {
+                buffer.set_position(offset);
+                let pointer = (buffer.read_u16_field(Field::Pointer)? & 0x3FFF) as usize;
+                // only backward pointers are allowed, which also rules out loops
+                if pointer >= lowest_pointer {
+                    return Err(error::invalid_value(Field::Pointer, offset, pointer as u32));
+                }
+                lowest_pointer = pointer;
+                if resume_at.is_none() {
+                    resume_at = Some(buffer.position());
+                }
+                buffer.set_position(pointer);
+            }
+            _ => return Err(error::invalid_value(Field::Label, offset, length as u32)),
+        }
+    }
+
+    Ok(resume_at.unwrap_or(buffer.position()))
+}
+
+fn fmt_labels<'l>(labels: impl Iterator<Item = &'l [u8]>, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut is_root = true;
+    for label in labels {
+        is_root = false;
+        for &octet in label {
+            match octet {
+                b'.' | b'\\' | b'"' | b'(' | b')' | b';' | b'@' | b'$' => write!(f, "\\{}", octet as char)?,
+                0x21..=0x7E => write!(f, "{}", octet as char)?,
+                _           => write!(f, "\\{:03}", octet)?,
+            }
+        }
+        write!(f, ".")?;
+    }
+    if is_root {
+        write!(f, ".")?;
+    }
+    Ok(())
+}
+
+/// A name borrowed from the message it was found in, possibly compressed.
+#[derive(Debug, Clone, Copy)]
+pub struct NameRef<'a> {
+    message: &'a [u8],
+    offset:  usize,
+}
+
+impl<'a> NameRef<'a> {
+    /// Checks the name at `offset` and returns the offset just past it.
+    pub(crate) fn validate(message: &'a [u8], offset: usize) -> Result<usize, DecodeError> {
+        walk_labels(message, offset, |_| ())
+    }
+
+    /// Reads a name that [`NameRef::validate`] has accepted, returning it with the offset just past it.
+    pub(crate) fn read_validated(message: &'a [u8], offset: usize) -> (Self, usize) {
+        let mut end = offset;
+        loop {
+            match message[end] {
+                0                                               => break (Self { message, offset }, end + 1),
+                length if length & POINTER_MASK == POINTER_MASK => break (Self { message, offset }, end + 2),
+                length                                          => end += length as usize + 1,
+            }
+        }
+    }
+
+    pub fn labels(&self) -> Labels<'a> {
+        Labels { message: self.message, offset: self.offset }
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.labels().next().is_none()
+    }
+
+    pub fn to_name(&self) -> Name {
+        Name { labels: self.labels().map(<[u8]>::to_vec).collect() }
+    }
+}
+
+impl PartialEq<Name> for NameRef<'_> {
+    fn eq(&self, other: &Name) -> bool {
+        let mut labels = self.labels();
+        other.labels.iter().all(|right| labels.next().is_some_and(|left| left.eq_ignore_ascii_case(right)))
+            && labels.next().is_none()
+    }
+}
+
+impl core::fmt::Display for NameRef<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_labels(self.labels(), f)
+    }
+}
+
+/// Labels of a [`NameRef`], with compression pointers followed.
+#[derive(Debug, Clone)]
+pub struct Labels<'a> {
+    message: &'a [u8],
+    offset:  usize,
+}
+
+impl<'a> Iterator for Labels<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let length = self.message[self.offset] as usize;
+            if length == 0 {
+                return None;
+            }
+            if length as u8 & POINTER_MASK == POINTER_MASK {
+                self.offset = (u16::from_be_bytes([self.message[self.offset], self.message[self.offset + 1]]) & 0x3FFF) as usize;
+                continue;
+            }
+            let label = &self.message[self.offset + 1..self.offset + 1 + length];
+            self.offset += length + 1;
+            return Some(label);
+        }
+    }
+}
+
+impl PartialEq for Name {
+    fn eq(&self, other: &Self) -> bool {
+        self.labels.len() == other.labels.len()
+            && self.labels.iter()
+                .zip(other.labels.iter())
+                .all(|(left, right)| left.eq_ignore_ascii_case(right))
+    }
+}
+
+impl Eq for Name {}
+
+impl core::hash::Hash for Name {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        for label in &self.labels {
+            label.to_ascii_lowercase().hash(state);
+        }
+    }
+}
+
+impl PartialOrd for Name {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Canonical DNS name order (RFC 4034 §6.1).
+impl Ord for Name {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.labels.iter().rev()
+            .map(|label| label.to_ascii_lowercase())
+            .cmp(other.labels.iter().rev().map(|label| label.to_ascii_lowercase()))
+    }
+}
+
+impl core::fmt::Display for Name {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_labels(self.labels.iter().map(Vec::as_slice), f)
+    }
+}
+
+impl core::str::FromStr for Name {
+    type Err = FromError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "." {
+            return Ok(Self::root());
+        }
+        let mut labels = vec![];
+        let mut label = vec![];
+        let mut bytes = s.bytes();
+        while let Some(byte) = bytes.next() {
+            match byte {
+                b'.' => {
+                    labels.push(core::mem::take(&mut label));
+                }
+                b'\\' => {
+                    let escaped = bytes.next().ok_or(FromError::InvalidEscape)?;
+                    if escaped.is_ascii_digit() {
+                        let digits = [escaped, bytes.next().ok_or(FromError::InvalidEscape)?, bytes.next().ok_or(FromError::InvalidEscape)?];
+                        if !digits.iter().all(u8::is_ascii_digit) {
+                            return Err(FromError::InvalidEscape);
+                        }
+                        let value = digits.iter().fold(0_u16, |value, digit| value * 10 + (digit - b'0') as u16);
+                        label.push(u8::try_from(value).map_err(|_| FromError::InvalidEscape)?);
+                    } else {
+                        label.push(escaped);
+                    }
+                }
+                b => label.push(b),
+            }
+        }
+        if !label.is_empty() {
+            labels.push(label);
+        }
+        Self::from_labels(labels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_parses_from_str() {
+        let name: Name = "www.example.com.".parse().unwrap();
+        assert_eq!(name.labels(), &[b"www".to_vec(), b"example".to_vec(), b"com".to_vec()]);
+        assert_eq!("www.example.com".parse::<Name>(), Ok(name));
+        assert_eq!(".".parse::<Name>(), Ok(Name::root()));
+        assert_eq!(r"a\.b.\065.".parse::<Name>().unwrap().labels(), &[b"a.b".to_vec(), b"A".to_vec()]);
+    }
+
+    #[test]
+    fn it_does_not_parse_invalid_str() {
+        assert_eq!("www..example.".parse::<Name>(), Err(FromError::EmptyLabel));
+        assert_eq!(format!("{}.", "a".repeat(64)).parse::<Name>(), Err(FromError::LabelTooLong(64)));
+        assert_eq!(r"a\25.".parse::<Name>(), Err(FromError::InvalidEscape));
+        assert_eq!(r"a\256.".parse::<Name>(), Err(FromError::InvalidEscape));
+    }
+
+    #[test]
+    fn it_formats_to_string() {
+        let name = Name::from_labels([b"a.b".to_vec(), vec![0x00], b"example".to_vec()]).unwrap();
+        assert_eq!(name.to_string(), r"a\.b.\000.example.");
+        assert_eq!(Name::root().to_string(), ".");
+    }
+
+    #[test]
+    fn it_compares_case_insensitively() {
+        let lower: Name = "example.com.".parse().unwrap();
+        let upper: Name = "EXAMPLE.Com.".parse().unwrap();
+        assert_eq!(lower, upper);
+        assert!("www.example.com.".parse::<Name>().unwrap().is_subdomain_of(&upper));
+        assert!(!"example.net.".parse::<Name>().unwrap().is_subdomain_of(&upper));
+    }
+
+    #[test]
+    fn it_orders_canonically() {
+        let mut names: Vec<Name> = ["z.example.", "example.", "a.example.", "yljkjljk.a.example."]
+            .iter()
+            .map(|name| name.parse().unwrap())
+            .collect();
+        names.sort();
+        let names: Vec<String> = names.iter().map(Name::to_string).collect();
+        assert_eq!(names, ["example.", "a.example.", "yljkjljk.a.example.", "z.example."]);
+    }
+
+    #[test]
+    fn it_reads_compressed_name() {
+        let message: &[u8] = &[
+            0x07, b'e', b'x', b'a', b'm', b'p', b'l', b'e', 0x00, // example.
+            0x03, b'w', b'w', b'w', 0xC0, 0x00,                   // www + pointer to 0
+            0xFF,
+        ];
+        let mut buffer = Reader::new(message);
+        buffer.set_position(9);
+        let name = Name::read_from(&mut buffer).unwrap();
+        assert_eq!(name, "www.example.".parse().unwrap());
+        assert_eq!(buffer.position(), 15);
+    }
+
+    #[test]
+    fn it_borrows_compressed_name() {
+        let message: &[u8] = &[
+            0x07, b'e', b'x', b'a', b'm', b'p', b'l', b'e', 0x00, // example.
+            0x03, b'W', b'W', b'W', 0xC0, 0x00,                   // WWW + pointer to 0
+        ];
+        assert_eq!(NameRef::validate(message, 9), Ok(15));
+        let (name, end) = NameRef::read_validated(message, 9);
+        assert_eq!(end, 15);
+        assert_eq!(name.labels().collect::<Vec<_>>(), vec![&b"WWW"[..], &b"example"[..]]);
+        assert_eq!(name, "www.example.".parse::<Name>().unwrap());
+        assert_ne!(name, "www.".parse::<Name>().unwrap());
+        assert_eq!(name.to_string(), "WWW.example.");
+        assert_eq!(name.to_name(), "www.example.".parse().unwrap());
+    }
+
+    #[test]
+    fn it_reports_error_when_pointer_loops() {
+        let message: &[u8] = &[0xC0, 0x00];
+        assert_eq!(Name::read_from(&mut Reader::new(message)), Err(error::invalid_value(Field::Pointer, 0, 0)), "should not follow self pointer");
+
+        let message: &[u8] = &[0x03, b'w', b'w'];
+        assert_eq!(Name::read_from(&mut Reader::new(message)), Err(error::truncated(Field::Label, 1)), "should not read lack label");
+    }
+
+    #[test]
+    fn it_writes_uncompressed_name() {
+        let mut buffer = vec![];
+        "www.example.".parse::<Name>().unwrap().write_to(&mut buffer);
+        assert_eq!(buffer, b"\x03www\x07example\x00");
+        assert_eq!(buffer.len(), "www.example.".parse::<Name>().unwrap().wire_length());
+    }
+}
@@ -0,0 +1,184 @@
+use alloc::vec;
+use crate::header::{id, one_bit_flag, opcode, rcode, Header};
+use crate::message::Message;
+use crate::name::Name;
+use crate::question::Question;
+use crate::record::rdata::RData;
+use crate::record::{class, rtype, Record};
+
+/// Announcement that a zone changed (RFC 1996), sent by a primary to its secondaries.
+pub struct NotifyBuilder {
+    id:    id::Id,
+    zone:  Name,
+    class: class::Class,
+    soa:   Option<Record>,
+}
+
+impl NotifyBuilder {
+    pub fn new(zone: Name) -> Self {
+        Self { id: id::Id(0), zone, class: class::Class::In, soa: None }
+    }
+
+    pub fn id(mut self, id: id::Id) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn class(mut self, class: class::Class) -> Self {
+        self.class = class;
+        self
+    }
+
+    /// Carries the new SOA in the answer section as a hint for the secondaries (RFC 1996 §3.7).
+    pub fn soa(mut self, soa: Record) -> Self {
+        self.soa = Some(soa);
+        self
+    }
+
+    pub fn build(self) -> Message {
+        Message {
+            header:      Header {
+                aa: one_bit_flag::Aa::FromAuthority,
+                ..Header::query(self.id, opcode::Opcode::Notify)
+            },
+            questions:   vec![Question {
+                name:   self.zone,
+                qtype:  rtype::Type::Soa,
+                qclass: self.class,
+            }],
+            answers:     self.soa.into_iter().collect(),
+            authorities: vec![],
+            additionals: vec![],
+        }
+    }
+}
+
+/// What an incoming NOTIFY announces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notify {
+    pub zone:   Name,
+    pub class:  class::Class,
+    /// Serial of the SOA hint, when the primary sent one.
+    pub serial: Option<u32>,
+}
+
+impl core::convert::TryFrom<&Message> for Notify {
+    /// RCODE to reject the NOTIFY with.
+    type Error = rcode::Rcode;
+
+    fn try_from(value: &Message) -> Result<Self, Self::Error> {
+        if value.header.opcode != opcode::Opcode::Notify || value.header.qr != one_bit_flag::Qr::Query {
+            return Err(rcode::Rcode::FormatError);
+        }
+        let question = match value.questions.as_slice() {
+            [question] if question.qtype == rtype::Type::Soa => question,
+            [_] => return Err(rcode::Rcode::NotImplemented),
+            _   => return Err(rcode::Rcode::FormatError),
+        };
+        let serial = match value.answers.as_slice() {
+            [] => None,
+            [Record { name, rdata: RData::Soa(soa), .. }] if name == &question.name => Some(soa.serial),
+            _ => return Err(rcode::Rcode::FormatError),
+        };
+        Ok(Self { zone: question.name.clone(), class: question.qclass, serial })
+    }
+}
+
+/// Acknowledges `query` with a response that echoes its question (RFC 1996 §4.7).
+pub fn acknowledge(query: &Message) -> Message {
+    reject(query, rcode::Rcode::NoError)
+}
+
+pub fn reject(query: &Message, rcode: rcode::Rcode) -> Message {
+    Message {
+        header:      Header {
+            qr: one_bit_flag::Qr::Response,
+            aa: one_bit_flag::Aa::FromAuthority,
+            tc: one_bit_flag::Tc::NotTruncated,
+            rcode,
+            ..query.header
+        },
+        questions:   query.questions.clone(),
+        answers:     vec![],
+        authorities: vec![],
+        additionals: vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::rdata::soa::Soa;
+    use pretty_assertions::assert_eq;
+
+    fn soa(serial: u32) -> Record {
+        Record {
+            name:  "example.".parse().unwrap(),
+            rtype: rtype::Type::Soa,
+            class: class::Class::In,
+            ttl:   3600,
+            rdata: RData::Soa(Soa {
+                mname:   "ns1.example.".parse().unwrap(),
+                rname:   "hostmaster.example.".parse().unwrap(),
+                serial,
+                refresh: 3600,
+                retry:   600,
+                expire:  604800,
+                minimum: 300,
+            }),
+        }
+    }
+
+    #[test]
+    fn it_builds_notify() {
+        let notify = NotifyBuilder::new("example.".parse().unwrap()).id(id::Id(7)).soa(soa(2024)).build();
+        assert_eq!(notify.header.opcode, opcode::Opcode::Notify);
+        assert_eq!(notify.header.qr, one_bit_flag::Qr::Query);
+        assert_eq!(notify.header.aa, one_bit_flag::Aa::FromAuthority);
+        assert_eq!(notify.questions[0].qtype, rtype::Type::Soa);
+        assert_eq!(notify.answers, vec![soa(2024)]);
+
+        let encoded: Vec<u8> = notify.try_into().unwrap();
+        let decoded = Message::try_from(encoded.as_slice()).unwrap();
+        assert_eq!(Notify::try_from(&decoded), Ok(Notify {
+            zone:   "example.".parse().unwrap(),
+            class:  class::Class::In,
+            serial: Some(2024),
+        }));
+    }
+
+    #[test]
+    fn it_reads_notify_without_soa() {
+        let notify = NotifyBuilder::new("example.".parse().unwrap()).build();
+        assert_eq!(Notify::try_from(&notify).unwrap().serial, None);
+    }
+
+    #[test]
+    fn it_does_not_read_invalid_notify() {
+        let notify = NotifyBuilder::new("example.".parse().unwrap()).build();
+        let query = Message { header: Header { opcode: opcode::Opcode::Query, ..notify.header }, ..notify.clone() };
+        assert_eq!(Notify::try_from(&query), Err(rcode::Rcode::FormatError));
+
+        let mut other_type = notify.clone();
+        other_type.questions[0].qtype = rtype::Type::A;
+        assert_eq!(Notify::try_from(&other_type), Err(rcode::Rcode::NotImplemented));
+
+        let other_owner = NotifyBuilder::new("example.net.".parse().unwrap()).soa(soa(1)).build();
+        assert_eq!(Notify::try_from(&other_owner), Err(rcode::Rcode::FormatError));
+    }
+
+    #[test]
+    fn it_acknowledges_notify() {
+        let notify = NotifyBuilder::new("example.".parse().unwrap()).id(id::Id(7)).soa(soa(2024)).build();
+        let response = acknowledge(&notify);
+        assert_eq!(response.header.id, id::Id(7));
+        assert_eq!(response.header.opcode, opcode::Opcode::Notify);
+        assert_eq!(response.header.qr, one_bit_flag::Qr::Response);
+        assert_eq!(response.header.aa, one_bit_flag::Aa::FromAuthority);
+        assert_eq!(response.header.rcode, rcode::Rcode::NoError);
+        assert_eq!(response.questions, notify.questions);
+        assert!(response.answers.is_empty());
+
+        assert_eq!(reject(&notify, rcode::Rcode::NotAuth).header.rcode, rcode::Rcode::NotAuth);
+    }
+}
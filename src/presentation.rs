@@ -0,0 +1,137 @@
+use core::fmt::{self, Display, Formatter};
+
+const BASE64_DIGITS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Finds the mnemonic of `value` in `table`.
+pub(crate) fn mnemonic_of<T: PartialEq>(table: &[(&'static str, T)], value: &T) -> Option<&'static str> {
+    table.iter().find(|(_, v)| v == value).map(|(mnemonic, _)| *mnemonic)
+}
+
+/// Finds the value named by `s` in `table`, ignoring ASCII case.
+pub(crate) fn from_mnemonic<T: Copy>(table: &[(&'static str, T)], s: &str) -> Option<T> {
+    table.iter().find(|(mnemonic, _)| mnemonic.eq_ignore_ascii_case(s)).map(|(_, value)| *value)
+}
+
+/// Parses the generic form of RFC 3597 §5, such as `TYPE65280` for `prefix` `TYPE`.
+pub(crate) fn parse_generic(prefix: &str, s: &str) -> Option<u16> {
+    let value = s.get(prefix.len()..).filter(|_| s[..prefix.len()].eq_ignore_ascii_case(prefix))?;
+    // u16::from_str would also take a sign
+    if !value.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
+
+/// Writes `mnemonic`, or `value` for codes without one.
+pub(crate) fn fmt_mnemonic(mnemonic: Option<&str>, value: impl Display, f: &mut Formatter<'_>) -> fmt::Result {
+    match mnemonic {
+        Some(mnemonic) => f.write_str(mnemonic),
+        None           => write!(f, "{}", value),
+    }
+}
+
+/// Writes `bytes` as a quoted character-string (RFC 1035 §5.1).
+pub(crate) fn fmt_character_string(bytes: &[u8], f: &mut Formatter<'_>) -> fmt::Result {
+    f.write_str("\"")?;
+    for &octet in bytes {
+        match octet {
+            b'"' | b'\\' => write!(f, "\\{}", octet as char)?,
+            0x20..=0x7E  => write!(f, "{}", octet as char)?,
+            _            => write!(f, "\\{:03}", octet)?,
+        }
+    }
+    f.write_str("\"")
+}
+
+pub(crate) fn fmt_base64(bytes: &[u8], f: &mut Formatter<'_>) -> fmt::Result {
+    for chunk in bytes.chunks(3) {
+        let octets = [0, chunk[0], chunk.get(1).copied().unwrap_or(0), chunk.get(2).copied().unwrap_or(0)];
+        let bits = u32::from_be_bytes(octets);
+        for i in 0..4 {
+            let digit = if i <= chunk.len() {
+                BASE64_DIGITS[(bits >> (18 - 6 * i) & 0x3F) as usize]
+            } else {
+                b'='
+            };
+            write!(f, "{}", digit as char)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes the length of `bytes`, then their base64 form unless they are empty, as dig prints
+/// the MAC and other data of TSIG and TKEY.
+pub(crate) fn fmt_sized_base64(bytes: &[u8], f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", bytes.len())?;
+    if !bytes.is_empty() {
+        f.write_str(" ")?;
+        fmt_base64(bytes, f)?;
+    }
+    Ok(())
+}
+
+/// Writes RDATA in the generic form of RFC 3597 §5.
+pub(crate) fn fmt_generic_rdata(bytes: &[u8], f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "\\# {}", bytes.len())?;
+    if !bytes.is_empty() {
+        f.write_str(" ")?;
+    }
+    bytes.iter().try_for_each(|octet| write!(f, "{:02X}", octet))
+}
+
+/// Writes seconds since the epoch as YYYYMMDDHHmmSS in UTC (RFC 4034 §3.2).
+pub(crate) fn fmt_time(seconds: u32, f: &mut Formatter<'_>) -> fmt::Result {
+    let (year, month, day) = civil_from_days(seconds / 86400);
+    let seconds = seconds % 86400;
+    write!(f, "{:04}{:02}{:02}{:02}{:02}{:02}", year, month, day, seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+/// Converts days since 1970-01-01 to a proleptic Gregorian date.
+fn civil_from_days(days: u32) -> (u32, u32, u32) {
+    // eras of 400 years starting on March 1st, 0000 (http://howardhinnant.github.io/date_algorithms.html)
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 { month_from_march + 3 } else { month_from_march - 9 };
+    let year = year_of_era + era * 400 + (month <= 2) as u32;
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    struct Formatted<F: Fn(&mut Formatter<'_>) -> fmt::Result>(F);
+
+    impl<F: Fn(&mut Formatter<'_>) -> fmt::Result> Display for Formatted<F> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            (self.0)(f)
+        }
+    }
+
+    #[test]
+    fn it_formats_base64() {
+        assert_eq!(Formatted(|f| fmt_base64(b"", f)).to_string(),       ""        );
+        assert_eq!(Formatted(|f| fmt_base64(b"f", f)).to_string(),      "Zg=="    );
+        assert_eq!(Formatted(|f| fmt_base64(b"fo", f)).to_string(),     "Zm8="    );
+        assert_eq!(Formatted(|f| fmt_base64(b"foobar", f)).to_string(), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn it_formats_character_string() {
+        let formatted = Formatted(|f| fmt_character_string(b"say \"hi\"\\\n", f)).to_string();
+        assert_eq!(formatted, r#""say \"hi\"\\\010""#);
+    }
+
+    #[test]
+    fn it_formats_time() {
+        assert_eq!(Formatted(|f| fmt_time(0, f)).to_string(),          "19700101000000");
+        assert_eq!(Formatted(|f| fmt_time(951_782_400, f)).to_string(), "20000229000000");
+        assert_eq!(Formatted(|f| fmt_time(u32::MAX, f)).to_string(),   "21060207062815");
+    }
+}
@@ -0,0 +1,83 @@
+use crate::reader::Reader;
+use crate::error::{DecodeError, Field};
+use crate::name::{Name, NameRef};
+use crate::record::{class, rtype};
+use crate::sink::Sink;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Question {
+    pub name:   Name,
+    pub qtype:  rtype::Type,
+    pub qclass: class::Class,
+}
+
+impl Question {
+    pub(crate) fn read_from(buffer: &mut Reader) -> Result<Self, DecodeError> {
+        let name = Name::read_from(buffer)?;
+        let qtype = rtype::Type::from(buffer.read_u16_field(Field::Type)?);
+        let qclass = class::Class::from(buffer.read_u16_field(Field::Class)?);
+
+        Ok(Self { name, qtype, qclass })
+    }
+
+    pub(crate) fn write_to(&self, buffer: &mut impl Sink) {
+        self.name.write_to(buffer);
+        buffer.put_slice(&u16::from(self.qtype).to_be_bytes());
+        buffer.put_slice(&u16::from(self.qclass).to_be_bytes());
+    }
+}
+
+/// A question borrowed from the message it was found in.
+#[derive(Debug, Clone, Copy)]
+pub struct QuestionRef<'a> {
+    pub name:   NameRef<'a>,
+    pub qtype:  rtype::Type,
+    pub qclass: class::Class,
+}
+
+impl<'a> QuestionRef<'a> {
+    /// Checks the question at `offset` and returns the offset just past it.
+    pub(crate) fn validate(message: &'a [u8], offset: usize) -> Result<usize, DecodeError> {
+        let mut buffer = Reader::new(message);
+        buffer.set_position(NameRef::validate(message, offset)?);
+        buffer.read_u16_field(Field::Type)?;
+        buffer.read_u16_field(Field::Class)?;
+        Ok(buffer.position())
+    }
+
+    /// Reads a question that [`QuestionRef::validate`] has accepted, returning it with the offset just past it.
+    pub(crate) fn read_validated(message: &'a [u8], offset: usize) -> (Self, usize) {
+        let (name, at) = NameRef::read_validated(message, offset);
+        let qtype = rtype::Type::from(u16::from_be_bytes([message[at], message[at + 1]]));
+        let qclass = class::Class::from(u16::from_be_bytes([message[at + 2], message[at + 3]]));
+        (Self { name, qtype, qclass }, at + 4)
+    }
+
+    pub fn to_question(&self) -> Question {
+        Question { name: self.name.to_name(), qtype: self.qtype, qclass: self.qclass }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_reads_and_writes_question() {
+        let buffer: &[u8] = &[
+            0x03, b'w', b'w', b'w', 0x00, // QNAME  = www.
+            0x00, 0x1C,                   // QTYPE  = AAAA
+            0x00, 0x01,                   // QCLASS = IN
+        ];
+        let question = Question::read_from(&mut Reader::new(buffer)).unwrap();
+        assert_eq!(question, Question {
+            name:   "www.".parse().unwrap(),
+            qtype:  rtype::Type::Aaaa,
+            qclass: class::Class::In,
+        });
+        let mut encoded = vec![];
+        question.write_to(&mut encoded);
+        assert_eq!(encoded, buffer);
+    }
+}
@@ -0,0 +1,73 @@
+use crate::error::{self, DecodeError, Field};
+
+/// Big endian reader over a message that reports the field a read failed in.
+#[derive(Debug, Clone)]
+pub(crate) struct Reader<'a> {
+    buffer:   &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(buffer: &'a [u8]) -> Self {
+        Self { buffer, position: 0 }
+    }
+
+    /// The whole message, which compression pointers refer into.
+    pub(crate) fn get_ref(&self) -> &'a [u8] {
+        self.buffer
+    }
+
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    pub(crate) fn set_position(&mut self, position: usize) {
+        self.position = position;
+    }
+
+    pub(crate) fn read_u8_field(&mut self, field: Field) -> Result<u8, DecodeError> {
+        self.read_array_field(field).map(u8::from_be_bytes)
+    }
+
+    pub(crate) fn read_u16_field(&mut self, field: Field) -> Result<u16, DecodeError> {
+        self.read_array_field(field).map(u16::from_be_bytes)
+    }
+
+    pub(crate) fn read_u32_field(&mut self, field: Field) -> Result<u32, DecodeError> {
+        self.read_array_field(field).map(u32::from_be_bytes)
+    }
+
+    pub(crate) fn read_slice_field(&mut self, length: usize, field: Field) -> Result<&'a [u8], DecodeError> {
+        let bytes = self.buffer.get(self.position..self.position + length)
+            .ok_or_else(|| error::truncated(field, self.position))?;
+        self.position += length;
+        Ok(bytes)
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn read_bytes_field(&mut self, length: usize, field: Field) -> Result<alloc::vec::Vec<u8>, DecodeError> {
+        self.read_slice_field(length, field).map(<[u8]>::to_vec)
+    }
+
+    fn read_array_field<const N: usize>(&mut self, field: Field) -> Result<[u8; N], DecodeError> {
+        let mut bytes = [0; N];
+        bytes.copy_from_slice(self.read_slice_field(N, field)?);
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_reads_fields() {
+        let buffer: &[u8] = &[0x01, 0x02, 0x03];
+        let mut reader = Reader::new(buffer);
+        assert_eq!(reader.read_u16_field(Field::Id), Ok(0x0102));
+        assert_eq!(reader.read_u16_field(Field::Type), Err(error::truncated(Field::Type, 2)));
+        assert_eq!(reader.position(), 2, "should not consume a truncated field");
+        assert_eq!(reader.read_slice_field(1, Field::Label), Ok(&[0x03][..]));
+    }
+}
@@ -0,0 +1,170 @@
+use crate::reader::Reader;
+use crate::error::{self, DecodeError, EncodeError, Field, Section};
+use crate::name::{Name, NameRef};
+use crate::sink::Sink;
+
+pub mod class;
+pub mod parse;
+pub mod rdata;
+pub mod rtype;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub name:  Name,
+    pub rtype: rtype::Type,
+    pub class: class::Class,
+    pub ttl:   u32,
+    pub rdata: rdata::RData,
+}
+
+impl Record {
+    pub(crate) fn read_from(buffer: &mut Reader) -> Result<Self, DecodeError> {
+        let name = Name::read_from(buffer)?;
+        let rtype = rtype::Type::from(buffer.read_u16_field(Field::Type)?);
+        let class = class::Class::from(buffer.read_u16_field(Field::Class)?);
+        let ttl = buffer.read_u32_field(Field::Ttl)?;
+        let rdlength = buffer.read_u16_field(Field::RdLength)?;
+        let rdata = rdata::RData::read_from(rtype, rdlength, buffer)?;
+
+        Ok(Self { name, rtype, class, ttl, rdata })
+    }
+
+    pub(crate) fn write_to(&self, buffer: &mut impl Sink) -> Result<(), EncodeError> {
+        self.name.write_to(buffer);
+        buffer.put_slice(&u16::from(self.rtype).to_be_bytes());
+        buffer.put_slice(&u16::from(self.class).to_be_bytes());
+        buffer.put_slice(&self.ttl.to_be_bytes());
+        // RDLENGTH is filled in once RDATA has been written
+        let rdlength_at = buffer.written();
+        buffer.put_slice(&[0, 0]);
+        self.rdata.write_to(buffer)?;
+        let rdlength = buffer.written() - rdlength_at - 2;
+        let rdlength = u16::try_from(rdlength)
+            .map_err(|_| error::too_long(Field::RdLength, rdlength_at, rdlength))?;
+        buffer.patch(rdlength_at, &rdlength.to_be_bytes());
+        Ok(())
+    }
+
+    /// Parses one record in master-file syntax (RFC 1035 §5.1), completing relative names with `origin`.
+    /// The class defaults to IN, while the TTL must be given.
+    pub fn parse(s: &str, origin: &Name) -> Result<Self, parse::ParseError> {
+        let mut entries = parse::entries(s);
+        let entry = entries.next().transpose()?
+            .ok_or(parse::ParseError::Missing { line: 1, expected: "record" })?;
+        if let Some(next) = entries.next().transpose()? {
+            return Err(parse::ParseError::TrailingTokens { line: next.line, token: next.tokens[0].text.into() });
+        }
+        let defaults = parse::Defaults { origin, owner: None, ttl: None, class: None };
+        parse::parse_entry(&entry, &defaults)
+    }
+}
+
+impl core::str::FromStr for Record {
+    type Err = parse::ParseError;
+
+    /// Parses one record in master-file syntax, with names relative to the root.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, &Name::root())
+    }
+}
+
+/// One line of a zone file: owner, TTL, class, type and RDATA separated by tabs, as dig prints records.
+impl core::fmt::Display for Record {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}\t{}\t{}\t{}\t{}", self.name, self.ttl, self.class, self.rtype, self.rdata)
+    }
+}
+
+/// A record borrowed from the message it was found in. RDATA is kept in wire form
+/// until [`RecordRef::to_record`] decodes it.
+#[derive(Debug, Clone, Copy)]
+pub struct RecordRef<'a> {
+    pub name:  NameRef<'a>,
+    pub rtype: rtype::Type,
+    pub class: class::Class,
+    pub ttl:   u32,
+    pub rdata: &'a [u8],
+    message:   &'a [u8],
+    rdata_at:  usize,
+    section:   Section,
+}
+
+impl<'a> RecordRef<'a> {
+    /// Checks the framing of the record at `offset` and returns the offset just past it.
+    /// RDATA is only checked to fit in `message`.
+    pub(crate) fn validate(message: &'a [u8], offset: usize) -> Result<usize, DecodeError> {
+        let mut buffer = Reader::new(message);
+        buffer.set_position(NameRef::validate(message, offset)?);
+        let rtype = rtype::Type::from(buffer.read_u16_field(Field::Type)?);
+        buffer.read_u16_field(Field::Class)?;
+        buffer.read_u32_field(Field::Ttl)?;
+        let rdlength = buffer.read_u16_field(Field::RdLength)? as usize;
+        let rdata_at = buffer.position();
+        if rdata_at + rdlength > message.len() {
+            return Err(error::truncated(Field::RData(rtype), rdata_at));
+        }
+        Ok(rdata_at + rdlength)
+    }
+
+    /// Reads a record that [`RecordRef::validate`] has accepted, returning it with the offset just past it.
+    pub(crate) fn read_validated(message: &'a [u8], offset: usize, section: Section) -> (Self, usize) {
+        let (name, at) = NameRef::read_validated(message, offset);
+        let rtype = rtype::Type::from(u16::from_be_bytes([message[at], message[at + 1]]));
+        let class = class::Class::from(u16::from_be_bytes([message[at + 2], message[at + 3]]));
+        let ttl = u32::from_be_bytes([message[at + 4], message[at + 5], message[at + 6], message[at + 7]]);
+        let rdlength = u16::from_be_bytes([message[at + 8], message[at + 9]]) as usize;
+        let rdata_at = at + 10;
+        let rdata = &message[rdata_at..rdata_at + rdlength];
+        (Self { name, rtype, class, ttl, rdata, message, rdata_at, section }, rdata_at + rdlength)
+    }
+
+    /// Decodes the RDATA, following compression pointers into the rest of the message.
+    pub fn to_record(&self) -> Result<Record, DecodeError> {
+        let mut buffer = Reader::new(self.message);
+        buffer.set_position(self.rdata_at);
+        let rdata = rdata::RData::read_from(self.rtype, self.rdata.len() as u16, &mut buffer)
+            .map_err(|e| e.in_section(self.section))?;
+
+        Ok(Record { name: self.name.to_name(), rtype: self.rtype, class: self.class, ttl: self.ttl, rdata })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_reads_and_writes_record() {
+        let buffer: &[u8] = &[
+            0x03, b'w', b'w', b'w', 0x00, // NAME     = www.
+            0x00, 0x01,                   // TYPE     = A
+            0x00, 0x01,                   // CLASS    = IN
+            0x00, 0x00, 0x01, 0x2C,       // TTL      = 300
+            0x00, 0x04,                   // RDLENGTH = 4
+            192, 0, 2, 1,                 // RDATA    = 192.0.2.1
+        ];
+        let record = Record::read_from(&mut Reader::new(buffer)).unwrap();
+        assert_eq!(record, Record {
+            name:  "www.".parse().unwrap(),
+            rtype: rtype::Type::A,
+            class: class::Class::In,
+            ttl:   300,
+            rdata: rdata::RData::A(core::net::Ipv4Addr::new(192, 0, 2, 1)),
+        });
+        let mut encoded = vec![];
+        record.write_to(&mut encoded).unwrap();
+        assert_eq!(encoded, buffer);
+    }
+
+    #[test]
+    fn it_reports_error_when_record_lack() {
+        let buffer: &[u8] = &[
+            0x03, b'w', b'w', b'w', 0x00, // NAME     = www.
+            0x00, 0x01,                   // TYPE     = A
+            0x00, 0x01,                   // CLASS    = IN
+            0x00, 0x00, 0x01,             // TTL (!!!LACK!!! length == 32bit == 4byte)
+        ];
+        assert_eq!(Record::read_from(&mut Reader::new(buffer)), Err(error::truncated(Field::Ttl, 9)));
+    }
+}
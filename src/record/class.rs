@@ -0,0 +1,112 @@
+use alloc::{format, string::String};
+use crate::error::UnknownMnemonic;
+use crate::presentation;
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Class {
+    In,
+    Ch,
+    Hs,
+    None,
+    Any,
+    Unknown(u16),
+}
+
+impl Class {
+    pub fn to_hex_string(&self) -> String {
+        let value: u16 = (*self).into();
+        format!("{:04X}", value)
+    }
+}
+
+/// Mnemonics of RFC 1035 §3.2.4 and RFC 2136 §1.3.
+const MNEMONICS: [(&str, Class); 5] = [
+    ("IN",   Class::In  ),
+    ("CH",   Class::Ch  ),
+    ("HS",   Class::Hs  ),
+    ("NONE", Class::None),
+    ("ANY",  Class::Any ),
+];
+
+/// Mnemonic, or `CLASS` and the value for classes without one (RFC 3597 §5).
+impl core::fmt::Display for Class {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match presentation::mnemonic_of(&MNEMONICS, self) {
+            Some(mnemonic) => f.write_str(mnemonic),
+            None           => write!(f, "CLASS{}", u16::from(*self)),
+        }
+    }
+}
+
+/// Accepts the mnemonics in any case, and `CLASS` followed by the value in decimal.
+impl core::str::FromStr for Class {
+    type Err = UnknownMnemonic;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        presentation::from_mnemonic(&MNEMONICS, s)
+            .or_else(|| presentation::parse_generic("CLASS", s).map(Self::from))
+            .ok_or(UnknownMnemonic { kind: "CLASS" })
+    }
+}
+
+impl core::convert::From<u16> for Class {
+    fn from(value: u16) -> Self {
+        match value {
+            1   => Self::In  ,
+            3   => Self::Ch  ,
+            4   => Self::Hs  ,
+            254 => Self::None,
+            255 => Self::Any ,
+            v   => Self::Unknown(v),
+        }
+    }
+}
+
+impl core::convert::From<Class> for u16 {
+    fn from(value: Class) -> Self {
+        match value {
+            Class::In         => 1,
+            Class::Ch         => 3,
+            Class::Hs         => 4,
+            Class::None       => 254,
+            Class::Any        => 255,
+            Class::Unknown(v) => v,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_converts_to_raw_value() {
+        let value: u16 = Class::In.into();
+        assert_eq!(value, 1);
+        let value: u16 = Class::None.into();
+        assert_eq!(value, 254);
+        let value: u16 = Class::Any.into();
+        assert_eq!(value, 255);
+    }
+
+    #[test]
+    fn it_converts_from_u16() {
+        assert_eq!(Class::from(1_u16),    Class::In           );
+        assert_eq!(Class::from(254_u16),  Class::None         );
+        assert_eq!(Class::from(4096_u16), Class::Unknown(4096));
+    }
+
+    #[test]
+    fn it_displays_mnemonic() {
+        assert_eq!(Class::In.to_string(),            "IN"       );
+        assert_eq!(Class::Unknown(4096).to_string(), "CLASS4096");
+    }
+
+    #[test]
+    fn it_parses_mnemonic() {
+        assert_eq!("in".parse(),                  Ok(Class::In)                          );
+        assert_eq!("CLASS4096".parse(),           Ok(Class::Unknown(4096))               );
+        assert_eq!("CLASS65536".parse::<Class>(), Err(UnknownMnemonic { kind: "CLASS" }));
+    }
+}
@@ -0,0 +1,674 @@
+use alloc::{string::{String, ToString}, vec, vec::Vec};
+use core::net::{Ipv4Addr, Ipv6Addr};
+use core::str::FromStr;
+use crate::name::{self, Name};
+use crate::reader::Reader;
+use super::rdata::{algorithm, key, sig, soa, tkey, tsig, RData};
+use super::{class, rtype, Record};
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The text ended inside a quoted string.
+    UnterminatedString { line: usize },
+    /// A `)` without its `(`, or text ending inside parentheses.
+    UnbalancedParentheses { line: usize },
+    /// The entry ended where `expected` was due.
+    Missing { line: usize, expected: &'static str },
+    /// `token` is not a valid `expected`.
+    Invalid { line: usize, expected: &'static str, token: String },
+    InvalidName { line: usize, token: String, cause: name::FromError },
+    /// Tokens follow a complete entry.
+    TrailingTokens { line: usize, token: String },
+}
+
+impl ParseError {
+    /// Line of the text the error was found on, counting from 1.
+    pub fn line(&self) -> usize {
+        match self {
+            Self::UnterminatedString { line } | Self::UnbalancedParentheses { line } => *line,
+            Self::Missing { line, .. } | Self::Invalid { line, .. }                  => *line,
+            Self::InvalidName { line, .. } | Self::TrailingTokens { line, .. }       => *line,
+        }
+    }
+}
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnterminatedString { line }            => write!(f, "line {}: quoted string is not closed", line),
+            Self::UnbalancedParentheses { line }         => write!(f, "line {}: parentheses are not balanced", line),
+            Self::Missing { line, expected }             => write!(f, "line {}: missing {}", line, expected),
+            Self::Invalid { line, expected, token }      => write!(f, "line {}: invalid {} {:?}", line, expected, token),
+            Self::InvalidName { line, token, cause }     => write!(f, "line {}: invalid name {:?}: {}", line, token, cause),
+            Self::TrailingTokens { line, token }         => write!(f, "line {}: unexpected {:?}", line, token),
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// A token of master-file text (RFC 1035 §5.1), without its quotes and with escapes kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Token<'a> {
+    pub(crate) text:   &'a str,
+    pub(crate) quoted: bool,
+    /// Line the token is on, which may follow the first line of its entry within parentheses.
+    pub(crate) line:   usize,
+}
+
+impl Token<'_> {
+    pub(crate) fn invalid(&self, expected: &'static str) -> ParseError {
+        ParseError::Invalid { line: self.line, expected, token: self.text.to_string() }
+    }
+}
+
+/// Tokens of one entry: a line, or several lines joined by parentheses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Entry<'a> {
+    pub(crate) line:     usize,
+    /// Whether the entry starts with blanks, leaving out the owner.
+    pub(crate) indented: bool,
+    pub(crate) tokens:   Vec<Token<'a>>,
+}
+
+/// Splits master-file text into entries, dropping comments and blank lines.
+pub(crate) struct Entries<'a> {
+    text:     &'a str,
+    position: usize,
+    line:     usize,
+}
+
+pub(crate) fn entries(text: &str) -> Entries<'_> {
+    Entries { text, position: 0, line: 1 }
+}
+
+impl<'a> Entries<'a> {
+    fn next_entry(&mut self) -> Result<Option<Entry<'a>>, ParseError> {
+        let bytes = self.text.as_bytes();
+        let mut entry = Entry { line: self.line, indented: false, tokens: vec![] };
+        let mut at_line_start = true;
+        let mut open_line = None;
+        while let Some(&byte) = bytes.get(self.position) {
+            if at_line_start && entry.tokens.is_empty() {
+                entry.line = self.line;
+                entry.indented = byte == b' ' || byte == b'\t';
+            }
+            at_line_start = false;
+            match byte {
+                b' ' | b'\t' | b'\r' => self.position += 1,
+                b';' => {
+                    while bytes.get(self.position).is_some_and(|&byte| byte != b'\n') {
+                        self.position += 1;
+                    }
+                },
+                b'\n' => {
+                    self.position += 1;
+                    self.line += 1;
+                    if open_line.is_some() {
+                        continue;
+                    }
+                    if !entry.tokens.is_empty() {
+                        return Ok(Some(entry));
+                    }
+                    at_line_start = true;
+                },
+                b'(' => {
+                    if open_line.is_some() {
+                        return Err(ParseError::UnbalancedParentheses { line: self.line });
+                    }
+                    open_line = Some(self.line);
+                    self.position += 1;
+                },
+                b')' => {
+                    if open_line.take().is_none() {
+                        return Err(ParseError::UnbalancedParentheses { line: self.line });
+                    }
+                    self.position += 1;
+                },
+                b'"' => {
+                    let start = self.position + 1;
+                    let mut end = start;
+                    loop {
+                        match bytes.get(end) {
+                            Some(b'"')          => break,
+                            Some(b'\\')         => end += 2,
+                            Some(b'\n') | None  => return Err(ParseError::UnterminatedString { line: self.line }),
+                            Some(_)             => end += 1,
+                        }
+                    }
+                    entry.tokens.push(Token { text: &self.text[start..end], quoted: true, line: self.line });
+                    self.position = end + 1;
+                },
+                _ => {
+                    let start = self.position;
+                    while let Some(&byte) = bytes.get(self.position) {
+                        match byte {
+                            b' ' | b'\t' | b'\r' | b'\n' | b';' | b'(' | b')' | b'"' => break,
+                            b'\\' => self.position = (self.position + 2).min(bytes.len()),
+                            _     => self.position += 1,
+                        }
+                    }
+                    entry.tokens.push(Token { text: &self.text[start..self.position], quoted: false, line: self.line });
+                },
+            }
+        }
+        if let Some(line) = open_line {
+            return Err(ParseError::UnbalancedParentheses { line });
+        }
+        Ok(Some(entry).filter(|entry| !entry.tokens.is_empty()))
+    }
+}
+
+impl<'a> Iterator for Entries<'a> {
+    type Item = Result<Entry<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_entry().transpose()
+    }
+}
+
+/// Values for the fields an entry leaves out.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Defaults<'a> {
+    /// Completes relative names, and stands for `@`.
+    pub(crate) origin: &'a Name,
+    /// Owner of indented entries.
+    pub(crate) owner:  Option<&'a Name>,
+    pub(crate) ttl:    Option<u32>,
+    pub(crate) class:  Option<class::Class>,
+}
+
+/// Tokens of an entry being consumed from the front.
+pub(crate) struct Tokens<'t, 'a> {
+    tokens: &'t [Token<'a>],
+    line:   usize,
+}
+
+impl<'t, 'a> Tokens<'t, 'a> {
+    pub(crate) fn new(entry: &'t Entry<'a>) -> Self {
+        Self { tokens: &entry.tokens, line: entry.line }
+    }
+
+    pub(crate) fn peek(&self) -> Option<Token<'a>> {
+        self.tokens.first().copied()
+    }
+
+    pub(crate) fn next(&mut self, expected: &'static str) -> Result<Token<'a>, ParseError> {
+        let (token, rest) = self.tokens.split_first()
+            .ok_or(ParseError::Missing { line: self.line, expected })?;
+        self.tokens = rest;
+        Ok(*token)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Reports tokens left over.
+    pub(crate) fn finish(&self) -> Result<(), ParseError> {
+        match self.tokens.first() {
+            Some(token) => Err(ParseError::TrailingTokens { line: token.line, token: token.text.to_string() }),
+            None        => Ok(()),
+        }
+    }
+
+    /// Takes the remaining tokens as one text, with the line of the first of them.
+    fn join_rest(&mut self) -> (usize, String) {
+        let line = self.tokens.first().map_or(self.line, |token| token.line);
+        let text = self.tokens.iter().map(|token| token.text).collect();
+        self.tokens = &[];
+        (line, text)
+    }
+
+    pub(crate) fn parse<T: FromStr>(&mut self, expected: &'static str) -> Result<T, ParseError> {
+        let token = self.next(expected)?;
+        token.text.parse().map_err(|_| token.invalid(expected))
+    }
+
+    pub(crate) fn name(&mut self, origin: &Name) -> Result<Name, ParseError> {
+        let token = self.next("name")?;
+        parse_name(token.text, origin)
+            .map_err(|cause| ParseError::InvalidName { line: token.line, token: token.text.to_string(), cause })
+    }
+
+    pub(crate) fn ttl(&mut self, expected: &'static str) -> Result<u32, ParseError> {
+        let token = self.next(expected)?;
+        parse_ttl(token.text).ok_or_else(|| token.invalid(expected))
+    }
+
+    fn time(&mut self, expected: &'static str) -> Result<u32, ParseError> {
+        let token = self.next(expected)?;
+        parse_time(token.text).ok_or_else(|| token.invalid(expected))
+    }
+
+    fn character_string(&mut self) -> Result<Vec<u8>, ParseError> {
+        let token = self.next("character-string")?;
+        unescape(token.text)
+            .filter(|string| string.len() <= u8::MAX as usize)
+            .ok_or_else(|| token.invalid("character-string"))
+    }
+
+    /// Decodes the remaining tokens as one base64 text, as keys and signatures may be split.
+    fn base64_rest(&mut self, expected: &'static str) -> Result<Vec<u8>, ParseError> {
+        let (line, text) = self.join_rest();
+        decode_base64(&text).ok_or(ParseError::Invalid { line, expected, token: text })
+    }
+
+    /// Decodes a length followed by that many octets in base64, the second left out when empty.
+    fn sized_base64(&mut self, expected: &'static str) -> Result<Vec<u8>, ParseError> {
+        let length: u16 = self.parse(expected)?;
+        if length == 0 {
+            return Ok(vec![]);
+        }
+        let token = self.next(expected)?;
+        decode_base64(token.text)
+            .filter(|bytes| bytes.len() == length as usize)
+            .ok_or_else(|| token.invalid(expected))
+    }
+}
+
+/// Parses an entry into a record, taking what it leaves out from `defaults`.
+pub(crate) fn parse_entry(entry: &Entry, defaults: &Defaults) -> Result<Record, ParseError> {
+    let mut tokens = Tokens::new(entry);
+    let name = match (entry.indented, defaults.owner) {
+        (true, Some(owner)) => owner.clone(),
+        (true, None)        => return Err(ParseError::Missing { line: entry.line, expected: "owner" }),
+        (false, _)          => tokens.name(defaults.origin)?,
+    };
+
+    // TTL and class may come in either order (RFC 1035 §5.1)
+    let mut ttl = None;
+    let mut class = None;
+    for _ in 0..2 {
+        let Some(token) = tokens.peek() else { break };
+        if ttl.is_none() && token.text.starts_with(|c: char| c.is_ascii_digit()) {
+            ttl = Some(tokens.ttl("TTL")?);
+        } else if class.is_none() && !token.quoted {
+            match token.text.parse() {
+                Ok(parsed) => {
+                    tokens.next("class")?;
+                    class = Some(parsed);
+                },
+                Err(_) => break,
+            }
+        }
+    }
+    let ttl = ttl.or(defaults.ttl).ok_or(ParseError::Missing { line: entry.line, expected: "TTL" })?;
+    let class = class.or(defaults.class).unwrap_or(class::Class::In);
+    let rtype: rtype::Type = tokens.parse("type")?;
+    let rdata = parse_rdata(rtype, &mut tokens, defaults.origin)?;
+    tokens.finish()?;
+
+    Ok(Record { name, rtype, class, ttl, rdata })
+}
+
+fn parse_rdata(rtype: rtype::Type, tokens: &mut Tokens, origin: &Name) -> Result<RData, ParseError> {
+    if tokens.peek().is_some_and(|token| token.text == "\\#" && !token.quoted) {
+        return parse_generic_rdata(rtype, tokens);
+    }
+    let rdata = match rtype {
+        rtype::Type::A     => RData::A(tokens.parse::<Ipv4Addr>("IPv4 address")?),
+        rtype::Type::Aaaa  => RData::Aaaa(tokens.parse::<Ipv6Addr>("IPv6 address")?),
+        rtype::Type::Ns    => RData::Ns(tokens.name(origin)?),
+        rtype::Type::Cname => RData::Cname(tokens.name(origin)?),
+        rtype::Type::Ptr   => RData::Ptr(tokens.name(origin)?),
+        rtype::Type::Mx    => RData::Mx { preference: tokens.parse("preference")?, exchange: tokens.name(origin)? },
+        rtype::Type::Txt   => {
+            let mut strings = vec![tokens.character_string()?];
+            while !tokens.is_empty() {
+                strings.push(tokens.character_string()?);
+            }
+            RData::Txt(strings)
+        },
+        rtype::Type::Soa   => RData::Soa(soa::Soa {
+            mname:   tokens.name(origin)?,
+            rname:   tokens.name(origin)?,
+            serial:  tokens.parse("serial")?,
+            refresh: tokens.ttl("refresh")?,
+            retry:   tokens.ttl("retry")?,
+            expire:  tokens.ttl("expire")?,
+            minimum: tokens.ttl("minimum")?,
+        }),
+        rtype::Type::Sig   => RData::Sig(sig::Sig {
+            type_covered: tokens.parse("type covered")?,
+            algorithm:    algorithm::Algorithm::from(tokens.parse::<u8>("algorithm")?),
+            labels:       tokens.parse("labels")?,
+            original_ttl: tokens.ttl("original TTL")?,
+            expiration:   tokens.time("expiration")?,
+            inception:    tokens.time("inception")?,
+            key_tag:      tokens.parse("key tag")?,
+            signer_name:  tokens.name(origin)?,
+            signature:    tokens.base64_rest("signature")?,
+        }),
+        rtype::Type::Key   => RData::Key(key::Key {
+            flags:      tokens.parse("flags")?,
+            protocol:   tokens.parse("protocol")?,
+            algorithm:  algorithm::Algorithm::from(tokens.parse::<u8>("algorithm")?),
+            public_key: tokens.base64_rest("public key")?,
+        }),
+        rtype::Type::Tkey  => RData::Tkey(tkey::Tkey {
+            algorithm:  tokens.name(origin)?,
+            inception:  tokens.time("inception")?,
+            expiration: tokens.time("expiration")?,
+            mode:       tkey::mode::Mode::from(tokens.parse::<u16>("mode")?),
+            error:      tokens.parse("error")?,
+            key:        tokens.sized_base64("key")?,
+            other:      tokens.sized_base64("other data")?,
+        }),
+        rtype::Type::Tsig  => {
+            let algorithm = tokens.name(origin)?;
+            let token = tokens.next("time signed")?;
+            let time_signed = token.text.parse::<u64>().ok()
+                .filter(|time_signed| *time_signed <= tsig::MAX_TIME_SIGNED)
+                .ok_or_else(|| token.invalid("time signed"))?;
+            RData::Tsig(tsig::Tsig {
+                algorithm,
+                time_signed,
+                fudge:       tokens.parse("fudge")?,
+                mac:         tokens.sized_base64("MAC")?,
+                original_id: tokens.parse("original ID")?,
+                error:       tokens.parse("error")?,
+                other:       tokens.sized_base64("other data")?,
+            })
+        },
+        _ => {
+            let token = tokens.next("RDATA")?;
+            return Err(token.invalid("RDATA, which needs the generic form for this type,"));
+        },
+    };
+    Ok(rdata)
+}
+
+/// Parses `\# length hex` (RFC 3597 §5), decoding the octets as the wire form of `rtype`.
+fn parse_generic_rdata(rtype: rtype::Type, tokens: &mut Tokens) -> Result<RData, ParseError> {
+    tokens.next("\\#")?;
+    let length: u16 = tokens.parse("RDATA length")?;
+    let (line, hex) = tokens.join_rest();
+    let Some(bytes) = decode_hex(&hex).filter(|bytes| bytes.len() == length as usize) else {
+        return Err(ParseError::Invalid { line, expected: "RDATA", token: hex });
+    };
+    RData::read_from(rtype, length, &mut Reader::new(&bytes))
+        .map_err(|_| ParseError::Invalid { line, expected: "RDATA", token: hex })
+}
+
+/// Parses a name, completing relative ones with `origin` and taking `@` for `origin` itself.
+pub(crate) fn parse_name(text: &str, origin: &Name) -> Result<Name, name::FromError> {
+    if text == "@" {
+        return Ok(origin.clone());
+    }
+    let name: Name = text.parse()?;
+    // a trailing dot makes the name absolute unless it is escaped
+    let escapes = text.bytes().rev().skip(1).take_while(|&byte| byte == b'\\').count();
+    if text == "." || (text.ends_with('.') && escapes % 2 == 0) {
+        return Ok(name);
+    }
+    Name::from_labels(name.labels().iter().chain(origin.labels()).cloned())
+}
+
+/// Parses a TTL in seconds, or in BIND's units such as `1h30m` (w, d, h, m, s).
+pub(crate) fn parse_ttl(text: &str) -> Option<u32> {
+    if text.bytes().all(|byte| byte.is_ascii_digit()) {
+        return text.parse().ok();
+    }
+    let mut ttl: u32 = 0;
+    let mut value: Option<u32> = None;
+    for byte in text.bytes() {
+        if byte.is_ascii_digit() {
+            value = Some(value.unwrap_or(0).checked_mul(10)?.checked_add((byte - b'0') as u32)?);
+            continue;
+        }
+        let unit = match byte.to_ascii_lowercase() {
+            b'w' => 604_800,
+            b'd' => 86_400,
+            b'h' => 3_600,
+            b'm' => 60,
+            b's' => 1,
+            _    => return None,
+        };
+        ttl = ttl.checked_add(value.take()?.checked_mul(unit)?)?;
+    }
+    match value {
+        Some(_) => None,
+        None    => Some(ttl),
+    }
+}
+
+/// Parses a SIG time as YYYYMMDDHHmmSS in UTC, or as seconds when not 14 digits long (RFC 4034 §3.2).
+fn parse_time(text: &str) -> Option<u32> {
+    if !text.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    if text.len() != 14 {
+        return text.parse().ok();
+    }
+    let field = |range: core::ops::Range<usize>| text[range].parse::<u32>().ok();
+    let (year, month, day) = (field(0..4)?, field(4..6)?, field(6..8)?);
+    let (hour, minute, second) = (field(8..10)?, field(10..12)?, field(12..14)?);
+    if year < 1970 || !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 59 {
+        return None;
+    }
+    let days = days_from_civil(year, month, day);
+    let seconds = days as u64 * 86_400 + (hour * 3_600 + minute * 60 + second) as u64;
+    u32::try_from(seconds).ok()
+}
+
+/// Converts a proleptic Gregorian date to days since 1970-01-01.
+fn days_from_civil(year: u32, month: u32, day: u32) -> u32 {
+    // the inverse of `presentation::civil_from_days`
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year % 400;
+    let month_from_march = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month_from_march + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Decodes `\X` and `\DDD` escapes (RFC 1035 §5.1).
+pub(crate) fn unescape(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = vec![];
+    let mut rest = text.bytes();
+    while let Some(byte) = rest.next() {
+        if byte != b'\\' {
+            bytes.push(byte);
+            continue;
+        }
+        let escaped = rest.next()?;
+        if !escaped.is_ascii_digit() {
+            bytes.push(escaped);
+            continue;
+        }
+        let digits = [escaped, rest.next()?, rest.next()?];
+        if !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        let value = digits.iter().fold(0_u16, |value, digit| value * 10 + (digit - b'0') as u16);
+        bytes.push(u8::try_from(value).ok()?);
+    }
+    Some(bytes)
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    text.as_bytes()
+        .chunks(2)
+        .map(|pair| u8::from_str_radix(core::str::from_utf8(pair).ok()?, 16).ok())
+        .collect()
+}
+
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let digits = text.trim_end_matches('=');
+    if !text.len().is_multiple_of(4) || text.len() - digits.len() > 2 {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(digits.len() * 3 / 4);
+    let mut bits: u32 = 0;
+    for (i, digit) in digits.bytes().enumerate() {
+        let value = match digit {
+            b'A'..=b'Z' => digit - b'A',
+            b'a'..=b'z' => digit - b'a' + 26,
+            b'0'..=b'9' => digit - b'0' + 52,
+            b'+'        => 62,
+            b'/'        => 63,
+            _           => return None,
+        };
+        bits = bits << 6 | value as u32;
+        if i % 4 == 3 {
+            bytes.extend_from_slice(&bits.to_be_bytes()[1..]);
+            bits = 0;
+        }
+    }
+    match digits.len() % 4 {
+        2 => bytes.push((bits >> 4) as u8),
+        3 => bytes.extend_from_slice(&((bits >> 2) as u16).to_be_bytes()),
+        _ => {},
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use pretty_assertions::assert_eq;
+
+    fn origin() -> Name {
+        "example.".parse().unwrap()
+    }
+
+    #[test]
+    fn it_splits_entries() {
+        let text = "a 1 A 192.0.2.1 ; comment\n\n  \"b c\" ( 2\n d ) \\; e\n";
+        let entries: Vec<Entry> = entries(text).collect::<Result<_, _>>().unwrap();
+        assert_eq!(entries, vec![
+            Entry { line: 1, indented: false, tokens: vec![
+                Token { text: "a",         quoted: false, line: 1 },
+                Token { text: "1",         quoted: false, line: 1 },
+                Token { text: "A",         quoted: false, line: 1 },
+                Token { text: "192.0.2.1", quoted: false, line: 1 },
+            ] },
+            Entry { line: 3, indented: true, tokens: vec![
+                Token { text: "b c",  quoted: true,  line: 3 },
+                Token { text: "2",    quoted: false, line: 3 },
+                Token { text: "d",    quoted: false, line: 4 },
+                Token { text: "\\;",  quoted: false, line: 4 },
+                Token { text: "e",    quoted: false, line: 4 },
+            ] },
+        ]);
+    }
+
+    #[test]
+    fn it_reports_unbalanced_entries() {
+        assert_eq!(entries("a ( 1\n").next(), Some(Err(ParseError::UnbalancedParentheses { line: 1 })));
+        assert_eq!(entries("a )").next(),     Some(Err(ParseError::UnbalancedParentheses { line: 1 })));
+        assert_eq!(entries("a \"b\n").next(), Some(Err(ParseError::UnterminatedString { line: 1 })));
+    }
+
+    #[test]
+    fn it_parses_record() {
+        let record = Record::parse("www.example. 300 IN A 192.0.2.1", &Name::root()).unwrap();
+        assert_eq!(record, Record {
+            name:  "www.example.".parse().unwrap(),
+            rtype: rtype::Type::A,
+            class: class::Class::In,
+            ttl:   300,
+            rdata: RData::A(Ipv4Addr::new(192, 0, 2, 1)),
+        });
+        assert_eq!("www.example. IN 300 A 192.0.2.1".parse(), Ok(record));
+    }
+
+    #[test]
+    fn it_completes_relative_names_with_origin() {
+        let record = Record::parse("@ 1h MX 10 mail", &origin()).unwrap();
+        assert_eq!(record.name, origin());
+        assert_eq!(record.ttl, 3_600);
+        assert_eq!(record.rdata, RData::Mx { preference: 10, exchange: "mail.example.".parse().unwrap() });
+
+        let record = Record::parse(r"a\. 0 CNAME b.", &origin()).unwrap();
+        assert_eq!(record.name.labels(), &[b"a.".to_vec(), b"example".to_vec()]);
+        assert_eq!(record.rdata, RData::Cname("b.".parse().unwrap()));
+    }
+
+    #[test]
+    fn it_parses_character_strings() {
+        let record = Record::parse(r#"txt 0 TXT "say \"hi\"" plain \255\;"#, &origin()).unwrap();
+        assert_eq!(record.rdata, RData::Txt(vec![b"say \"hi\"".to_vec(), b"plain".to_vec(), vec![0xFF, b';']]));
+    }
+
+    #[test]
+    fn it_parses_multiline_rdata() {
+        let text = "@ 0 SOA ns hostmaster (\n 2024010101 ; serial\n 1d 2h 4w 1h )";
+        let record = Record::parse(text, &origin()).unwrap();
+        assert_eq!(record.rdata, RData::Soa(soa::Soa {
+            mname:   "ns.example.".parse().unwrap(),
+            rname:   "hostmaster.example.".parse().unwrap(),
+            serial:  2024010101,
+            refresh: 86_400,
+            retry:   7_200,
+            expire:  2_419_200,
+            minimum: 3_600,
+        }));
+    }
+
+    #[test]
+    fn it_parses_generic_rdata() {
+        let record = Record::parse(r"a. 0 A \# 4 C0000201", &origin()).unwrap();
+        assert_eq!(record.rdata, RData::A(Ipv4Addr::new(192, 0, 2, 1)));
+        let record = Record::parse(r"a. 0 CLASS42 TYPE65280 \# 3 ABCD EF", &origin()).unwrap();
+        assert_eq!(record.class, class::Class::Unknown(42));
+        assert_eq!(record.rdata, RData::Unknown(vec![0xAB, 0xCD, 0xEF]));
+        let record = Record::parse(r"a. 0 ANY A \# 0", &origin()).unwrap();
+        assert_eq!((record.class, record.rdata), (class::Class::Any, RData::Empty));
+    }
+
+    #[test]
+    fn it_parses_what_it_displays() {
+        let records = [
+            "key.example.\t300\tIN\tKEY\t256 3 15 AAECAwQ=",
+            "sig.example.\t0\tANY\tSIG\tA 15 2 300 20240101000000 20231201000000 2345 key.example. Zm9vYmFy",
+            "tsig.\t0\tANY\tTSIG\thmac-sha256. 1700000000 300 2 AAE= 4660 BADTIME 0",
+            "tkey.\t0\tANY\tTKEY\tgss-tsig. 1700000000 1700003600 3 NOERROR 3 AQID 0",
+            "txt.example.\t60\tIN\tTXT\t\"a b\" \"\\\"\"",
+        ];
+        for text in records {
+            let record: Record = text.parse().unwrap();
+            assert_eq!(record.to_string(), text);
+        }
+    }
+
+    #[test]
+    fn it_reports_invalid_record() {
+        assert_eq!(
+            Record::parse("www 300 IN A 192.0.2", &origin()),
+            Err(ParseError::Invalid { line: 1, expected: "IPv4 address", token: "192.0.2".to_string() }),
+        );
+        assert_eq!(
+            Record::parse("www IN A 192.0.2.1", &origin()),
+            Err(ParseError::Missing { line: 1, expected: "TTL" }),
+        );
+        assert_eq!(
+            Record::parse("www 300 IN MX 10 mail extra", &origin()),
+            Err(ParseError::TrailingTokens { line: 1, token: "extra".to_string() }),
+        );
+        assert_eq!(
+            Record::parse("www..example. 300 IN A 192.0.2.1", &origin()),
+            Err(ParseError::InvalidName { line: 1, token: "www..example.".to_string(), cause: name::FromError::EmptyLabel }),
+        );
+        assert_eq!(
+            Record::parse(r"a. 0 A \# 4 C00002", &origin()),
+            Err(ParseError::Invalid { line: 1, expected: "RDATA", token: "C00002".to_string() }),
+        );
+    }
+
+    #[test]
+    fn it_parses_ttl_units() {
+        assert_eq!(parse_ttl("300"),    Some(300)   );
+        assert_eq!(parse_ttl("1H30m"),  Some(5_400) );
+        assert_eq!(parse_ttl("1w"),     Some(604_800));
+        assert_eq!(parse_ttl("1h30"),   None        );
+        assert_eq!(parse_ttl("h"),      None        );
+    }
+}
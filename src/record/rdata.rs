@@ -0,0 +1,219 @@
+use alloc::{vec, vec::Vec};
+use crate::reader::Reader;
+use core::net::{Ipv4Addr, Ipv6Addr};
+use crate::error::{self, DecodeError, EncodeError, Field};
+use crate::name::Name;
+use crate::presentation;
+use crate::sink::Sink;
+use super::rtype::Type;
+
+pub mod algorithm;
+pub mod key;
+pub mod sig;
+pub mod soa;
+pub mod tkey;
+pub mod tsig;
+
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RData {
+    A(Ipv4Addr),
+    Aaaa(Ipv6Addr),
+    Ns(Name),
+    Cname(Name),
+    Ptr(Name),
+    Mx { preference: u16, exchange: Name },
+    Txt(Vec<Vec<u8>>),
+    Soa(soa::Soa),
+    Sig(sig::Sig),
+    Key(key::Key),
+    Tkey(tkey::Tkey),
+    Tsig(tsig::Tsig),
+    /// RDLENGTH 0, as used by prerequisites and deletions in UPDATE messages.
+    Empty,
+    Unknown(Vec<u8>),
+}
+
+/// Writes `bytes` prefixed with their 16 bit length, `field` naming that length.
+pub(crate) fn write_sized_bytes(buffer: &mut impl Sink, bytes: &[u8], field: Field) -> Result<(), EncodeError> {
+    let length = u16::try_from(bytes.len())
+        .map_err(|_| error::too_long(field, buffer.written(), bytes.len()))?;
+    buffer.put_slice(&length.to_be_bytes());
+    buffer.put_slice(bytes);
+    Ok(())
+}
+
+impl RData {
+    pub(crate) fn read_from(rtype: Type, length: u16, buffer: &mut Reader) -> Result<Self, DecodeError> {
+        let start = buffer.position();
+        let end = start + length as usize;
+        if end > buffer.get_ref().len() {
+            return Err(error::truncated(Field::RData(rtype), start));
+        }
+        let rdata = Self::read_typed(rtype, length, buffer)?;
+        if buffer.position() != end {
+            return Err(error::length_mismatch(Field::RdLength, start, length as usize));
+        }
+        Ok(rdata)
+    }
+
+    fn read_typed(rtype: Type, length: u16, buffer: &mut Reader) -> Result<Self, DecodeError> {
+        if length == 0 {
+            return Ok(Self::Empty);
+        }
+        let field = Field::RData(rtype);
+        let end = buffer.position() + length as usize;
+        let rdata = match rtype {
+            Type::A     => Self::A(Ipv4Addr::from(buffer.read_u32_field(field)?)),
+            Type::Aaaa  => {
+                let octets: [u8; 16] = buffer.read_bytes_field(16, field)?.try_into().expect("read 16 octets");
+                Self::Aaaa(Ipv6Addr::from(octets))
+            },
+            Type::Ns    => Self::Ns(Name::read_from(buffer)?),
+            Type::Cname => Self::Cname(Name::read_from(buffer)?),
+            Type::Ptr   => Self::Ptr(Name::read_from(buffer)?),
+            Type::Mx    => Self::Mx {
+                preference: buffer.read_u16_field(field)?,
+                exchange: Name::read_from(buffer)?,
+            },
+            Type::Txt   => {
+                let mut strings = vec![];
+                while buffer.position() < end {
+                    let length = buffer.read_u8_field(field)?;
+                    strings.push(buffer.read_bytes_field(length as usize, field)?);
+                }
+                Self::Txt(strings)
+            },
+            Type::Soa   => Self::Soa(soa::Soa::read_from(buffer)?),
+            Type::Sig   => Self::Sig(sig::Sig::read_from(buffer, length)?),
+            Type::Key   => Self::Key(key::Key::read_from(buffer, length)?),
+            Type::Tkey  => Self::Tkey(tkey::Tkey::read_from(buffer)?),
+            Type::Tsig  => Self::Tsig(tsig::Tsig::read_from(buffer)?),
+            _           => Self::Unknown(buffer.read_bytes_field(length as usize, field)?),
+        };
+        Ok(rdata)
+    }
+
+    pub(crate) fn write_to(&self, buffer: &mut impl Sink) -> Result<(), EncodeError> {
+        match self {
+            Self::A(address)    => buffer.put_slice(&address.octets()),
+            Self::Aaaa(address) => buffer.put_slice(&address.octets()),
+            Self::Ns(name) | Self::Cname(name) | Self::Ptr(name) => name.write_to(buffer),
+            Self::Mx { preference, exchange } => {
+                buffer.put_slice(&preference.to_be_bytes());
+                exchange.write_to(buffer);
+            },
+            Self::Txt(strings) => {
+                for string in strings {
+                    let length = u8::try_from(string.len())
+                        .map_err(|_| error::too_long(Field::RData(Type::Txt), buffer.written(), string.len()))?;
+                    buffer.put_u8(length);
+                    buffer.put_slice(string);
+                }
+            },
+            Self::Soa(soa)   => soa.write_to(buffer),
+            Self::Sig(sig)   => sig.write_to(buffer),
+            Self::Key(key)   => key.write_to(buffer),
+            Self::Tkey(tkey) => tkey.write_to(buffer)?,
+            Self::Tsig(tsig) => tsig.write_to(buffer)?,
+            Self::Empty      => {},
+            Self::Unknown(bytes) => buffer.put_slice(bytes),
+        }
+        Ok(())
+    }
+}
+
+/// Presentation format of RFC 1035 §5.1; `Empty` and `Unknown` use the generic form of RFC 3597 §5.
+impl core::fmt::Display for RData {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::A(address)    => write!(f, "{}", address),
+            Self::Aaaa(address) => write!(f, "{}", address),
+            Self::Ns(name) | Self::Cname(name) | Self::Ptr(name) => write!(f, "{}", name),
+            Self::Mx { preference, exchange } => write!(f, "{} {}", preference, exchange),
+            Self::Txt(strings)  => {
+                for (i, string) in strings.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(" ")?;
+                    }
+                    presentation::fmt_character_string(string, f)?;
+                }
+                Ok(())
+            },
+            Self::Soa(soa)       => write!(f, "{}", soa),
+            Self::Sig(sig)       => write!(f, "{}", sig),
+            Self::Key(key)       => write!(f, "{}", key),
+            Self::Tkey(tkey)     => write!(f, "{}", tkey),
+            Self::Tsig(tsig)     => write!(f, "{}", tsig),
+            Self::Empty          => presentation::fmt_generic_rdata(&[], f),
+            Self::Unknown(bytes) => presentation::fmt_generic_rdata(bytes, f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_reads_typed_rdata() {
+        let buffer: &[u8] = &[192, 0, 2, 1];
+        let rdata = RData::read_from(Type::A, 4, &mut Reader::new(buffer));
+        assert_eq!(rdata.unwrap(), RData::A(Ipv4Addr::new(192, 0, 2, 1)));
+
+        let buffer: &[u8] = &[0x00, 0x0A, 0x02, b'm', b'x', 0x00];
+        let rdata = RData::read_from(Type::Mx, 6, &mut Reader::new(buffer));
+        assert_eq!(rdata.unwrap(), RData::Mx { preference: 10, exchange: "mx.".parse().unwrap() });
+
+        let buffer: &[u8] = &[0x01, b'a', 0x02, b'b', b'c'];
+        let rdata = RData::read_from(Type::Txt, 5, &mut Reader::new(buffer));
+        assert_eq!(rdata.unwrap(), RData::Txt(vec![b"a".to_vec(), b"bc".to_vec()]));
+    }
+
+    #[test]
+    fn it_reads_empty_and_unknown_rdata() {
+        let buffer: &[u8] = &[];
+        assert_eq!(RData::read_from(Type::A, 0, &mut Reader::new(buffer)).unwrap(), RData::Empty);
+
+        let buffer: &[u8] = &[0xDE, 0xAD];
+        let rdata = RData::read_from(Type::Unknown(65280), 2, &mut Reader::new(buffer));
+        assert_eq!(rdata.unwrap(), RData::Unknown(vec![0xDE, 0xAD]));
+    }
+
+    #[test]
+    fn it_reports_error_when_rdlength_mismatch() {
+        let buffer: &[u8] = &[192, 0, 2, 1, 0];
+        assert_eq!(
+            RData::read_from(Type::A, 5, &mut Reader::new(buffer)),
+            Err(error::length_mismatch(Field::RdLength, 0, 5)),
+            "should not ignore trailing bytes",
+        );
+
+        let buffer: &[u8] = &[192, 0, 2];
+        assert_eq!(
+            RData::read_from(Type::A, 4, &mut Reader::new(buffer)),
+            Err(error::truncated(Field::RData(Type::A), 0)),
+            "should not read beyond message",
+        );
+    }
+
+    #[test]
+    fn it_writes_rdata() {
+        let mut buffer = vec![];
+        RData::Txt(vec![b"a".to_vec(), b"bc".to_vec()]).write_to(&mut buffer).unwrap();
+        assert_eq!(buffer, [0x01, b'a', 0x02, b'b', b'c']);
+
+        let mut buffer = vec![];
+        RData::Aaaa("2001:db8::1".parse().unwrap()).write_to(&mut buffer).unwrap();
+        assert_eq!(buffer, [0x20, 0x01, 0x0D, 0xB8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01]);
+    }
+
+    #[test]
+    fn it_displays_presentation_format() {
+        assert_eq!(RData::Mx { preference: 10, exchange: "mx.".parse().unwrap() }.to_string(), "10 mx.");
+        assert_eq!(RData::Txt(vec![b"a b".to_vec(), b"\"".to_vec()]).to_string(),          r#""a b" "\"""#);
+        assert_eq!(RData::Unknown(vec![0xDE, 0xAD]).to_string(),                          r"\# 2 DEAD");
+        assert_eq!(RData::Empty.to_string(),                                             r"\# 0");
+    }
+}
@@ -0,0 +1,96 @@
+use alloc::{format, string::String};
+/// DNS Security Algorithm Numbers, shared by KEY, SIG and their DNSSEC successors.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    RsaMd5,
+    DiffieHellman,
+    DsaSha1,
+    RsaSha1,
+    DsaNsec3Sha1,
+    RsaSha1Nsec3Sha1,
+    RsaSha256,
+    RsaSha512,
+    EccGost,
+    EcdsaP256Sha256,
+    EcdsaP384Sha384,
+    Ed25519,
+    Ed448,
+    PrivateDns,
+    PrivateOid,
+    Unassigned(u8),
+}
+
+impl Algorithm {
+    pub fn to_hex_string(&self) -> String {
+        let value: u8 = (*self).into();
+        format!("{:02X}", value)
+    }
+}
+
+impl core::convert::From<u8> for Algorithm {
+    fn from(value: u8) -> Self {
+        match value {
+            1   => Self::RsaMd5          ,
+            2   => Self::DiffieHellman   ,
+            3   => Self::DsaSha1         ,
+            5   => Self::RsaSha1         ,
+            6   => Self::DsaNsec3Sha1    ,
+            7   => Self::RsaSha1Nsec3Sha1,
+            8   => Self::RsaSha256       ,
+            10  => Self::RsaSha512       ,
+            12  => Self::EccGost         ,
+            13  => Self::EcdsaP256Sha256 ,
+            14  => Self::EcdsaP384Sha384 ,
+            15  => Self::Ed25519         ,
+            16  => Self::Ed448           ,
+            253 => Self::PrivateDns      ,
+            254 => Self::PrivateOid      ,
+            v   => Self::Unassigned(v),
+        }
+    }
+}
+
+impl core::convert::From<Algorithm> for u8 {
+    fn from(value: Algorithm) -> Self {
+        match value {
+            Algorithm::RsaMd5           => 1,
+            Algorithm::DiffieHellman    => 2,
+            Algorithm::DsaSha1          => 3,
+            Algorithm::RsaSha1          => 5,
+            Algorithm::DsaNsec3Sha1     => 6,
+            Algorithm::RsaSha1Nsec3Sha1 => 7,
+            Algorithm::RsaSha256        => 8,
+            Algorithm::RsaSha512        => 10,
+            Algorithm::EccGost          => 12,
+            Algorithm::EcdsaP256Sha256  => 13,
+            Algorithm::EcdsaP384Sha384  => 14,
+            Algorithm::Ed25519          => 15,
+            Algorithm::Ed448            => 16,
+            Algorithm::PrivateDns       => 253,
+            Algorithm::PrivateOid       => 254,
+            Algorithm::Unassigned(v)    => v,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_converts_to_raw_value() {
+        let value: u8 = Algorithm::DiffieHellman.into();
+        assert_eq!(value, 2);
+        let value: u8 = Algorithm::Ed25519.into();
+        assert_eq!(value, 15);
+    }
+
+    #[test]
+    fn it_converts_from_u8() {
+        assert_eq!(Algorithm::from(8_u8),  Algorithm::RsaSha256     );
+        assert_eq!(Algorithm::from(13_u8), Algorithm::EcdsaP256Sha256);
+        assert_eq!(Algorithm::from(4_u8),  Algorithm::Unassigned(4)  );
+    }
+}
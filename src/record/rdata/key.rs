@@ -0,0 +1,107 @@
+use alloc::{vec, vec::Vec};
+use crate::reader::Reader;
+use crate::error::{DecodeError, Field};
+use crate::presentation;
+use crate::record::rtype;
+use crate::sink::Sink;
+use super::algorithm::Algorithm;
+
+/// The protocol octet, which RFC 3445 fixes to DNSSEC.
+pub const PROTOCOL_DNSSEC: u8 = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Key {
+    pub flags:      u16,
+    pub protocol:   u8,
+    pub algorithm:  Algorithm,
+    pub public_key: Vec<u8>,
+}
+
+impl Key {
+    /// Key tag referenced by SIG RRs (RFC 4034 Appendix B).
+    pub fn key_tag(&self) -> u16 {
+        let mut rdata = vec![];
+        rdata.extend_from_slice(&self.flags.to_be_bytes());
+        rdata.push(self.protocol);
+        rdata.push(self.algorithm.into());
+        rdata.extend_from_slice(&self.public_key);
+        if self.algorithm == Algorithm::RsaMd5 {
+            return match rdata.len() {
+                length if length >= 3 => u16::from_be_bytes([rdata[length - 3], rdata[length - 2]]),
+                _ => 0,
+            };
+        }
+        let accumulator = rdata.iter().enumerate().fold(0_u32, |accumulator, (index, &octet)| {
+            if index & 1 == 0 {
+                accumulator + ((octet as u32) << 8)
+            } else {
+                accumulator + octet as u32
+            }
+        });
+        ((accumulator + ((accumulator >> 16) & 0xFFFF)) & 0xFFFF) as u16
+    }
+
+    pub(crate) fn read_from(buffer: &mut Reader, length: u16) -> Result<Self, DecodeError> {
+        let field = Field::RData(rtype::Type::Key);
+        let flags = buffer.read_u16_field(field)?;
+        let protocol = buffer.read_u8_field(field)?;
+        let algorithm = Algorithm::from(buffer.read_u8_field(field)?);
+        let public_key = buffer.read_bytes_field((length as usize).saturating_sub(4), field)?;
+        Ok(Self { flags, protocol, algorithm, public_key })
+    }
+
+    pub(crate) fn write_to(&self, buffer: &mut impl Sink) {
+        buffer.put_slice(&self.flags.to_be_bytes());
+        buffer.put_u8(self.protocol);
+        buffer.put_u8(self.algorithm.into());
+        buffer.put_slice(&self.public_key);
+    }
+}
+
+impl core::fmt::Display for Key {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} {} {} ", self.flags, self.protocol, u8::from(self.algorithm))?;
+        presentation::fmt_base64(&self.public_key, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_reads_and_writes_key() {
+        let buffer: &[u8] = &[
+            0x02, 0x00,             // FLAGS     = zone key
+            0x03,                   // PROTOCOL  = DNSSEC
+            0x02,                   // ALGORITHM = Diffie-Hellman
+            0xAB, 0xCD, 0xEF,       // PUBLIC KEY
+        ];
+        let key = Key::read_from(&mut Reader::new(buffer), buffer.len() as u16).unwrap();
+        assert_eq!(key, Key {
+            flags:      0x0200,
+            protocol:   PROTOCOL_DNSSEC,
+            algorithm:  Algorithm::DiffieHellman,
+            public_key: vec![0xAB, 0xCD, 0xEF],
+        });
+        let mut encoded = vec![];
+        key.write_to(&mut encoded);
+        assert_eq!(encoded, buffer);
+    }
+
+    #[test]
+    fn it_calculates_key_tag() {
+        // RDATA is summed as big endian 16 bit words: 0100 0305 0103 0100 01(00)
+        let key = Key {
+            flags:      0x0100,
+            protocol:   PROTOCOL_DNSSEC,
+            algorithm:  Algorithm::RsaSha1,
+            public_key: vec![0x01, 0x03, 0x01, 0x00, 0x01],
+        };
+        assert_eq!(key.key_tag(), 0x0100 + 0x0305 + 0x0103 + 0x0100 + 0x0100);
+
+        let key = Key { algorithm: Algorithm::RsaMd5, ..key };
+        assert_eq!(key.key_tag(), 0x0100);
+    }
+}
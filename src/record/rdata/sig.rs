@@ -0,0 +1,104 @@
+use alloc::vec::Vec;
+use crate::reader::Reader;
+use crate::error::{DecodeError, Field};
+use crate::name::Name;
+use crate::presentation;
+use crate::record::rtype;
+use crate::sink::Sink;
+use super::algorithm::Algorithm;
+
+/// SIG RDATA (RFC 2535 §4.1), as used by SIG(0) transaction signatures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sig {
+    pub type_covered: rtype::Type,
+    pub algorithm:    Algorithm,
+    pub labels:       u8,
+    pub original_ttl: u32,
+    pub expiration:   u32,
+    pub inception:    u32,
+    pub key_tag:      u16,
+    pub signer_name:  Name,
+    pub signature:    Vec<u8>,
+}
+
+impl Sig {
+    pub(crate) fn read_from(buffer: &mut Reader, length: u16) -> Result<Self, DecodeError> {
+        let field = Field::RData(rtype::Type::Sig);
+        let end = buffer.position() + length as usize;
+        let type_covered = rtype::Type::from(buffer.read_u16_field(field)?);
+        let algorithm = Algorithm::from(buffer.read_u8_field(field)?);
+        let labels = buffer.read_u8_field(field)?;
+        let original_ttl = buffer.read_u32_field(field)?;
+        let expiration = buffer.read_u32_field(field)?;
+        let inception = buffer.read_u32_field(field)?;
+        let key_tag = buffer.read_u16_field(field)?;
+        let signer_name = Name::read_from(buffer)?;
+        let signature = buffer.read_bytes_field(end.saturating_sub(buffer.position()), field)?;
+        Ok(Self { type_covered, algorithm, labels, original_ttl, expiration, inception, key_tag, signer_name, signature })
+    }
+
+    /// Writes every field but the signature, which is the prefix of the signed data.
+    pub(crate) fn write_unsigned_to(&self, buffer: &mut impl Sink) {
+        buffer.put_slice(&u16::from(self.type_covered).to_be_bytes());
+        buffer.put_u8(self.algorithm.into());
+        buffer.put_u8(self.labels);
+        buffer.put_slice(&self.original_ttl.to_be_bytes());
+        buffer.put_slice(&self.expiration.to_be_bytes());
+        buffer.put_slice(&self.inception.to_be_bytes());
+        buffer.put_slice(&self.key_tag.to_be_bytes());
+        self.signer_name.write_to(buffer);
+    }
+
+    pub(crate) fn write_to(&self, buffer: &mut impl Sink) {
+        self.write_unsigned_to(buffer);
+        buffer.put_slice(&self.signature);
+    }
+}
+
+/// Times are printed as YYYYMMDDHHmmSS, like RRSIG (RFC 4034 §3.2).
+impl core::fmt::Display for Sig {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} {} {} {} ", self.type_covered, u8::from(self.algorithm), self.labels, self.original_ttl)?;
+        presentation::fmt_time(self.expiration, f)?;
+        f.write_str(" ")?;
+        presentation::fmt_time(self.inception, f)?;
+        write!(f, " {} {} ", self.key_tag, self.signer_name)?;
+        presentation::fmt_base64(&self.signature, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_reads_and_writes_sig() {
+        let buffer: &[u8] = &[
+            0x00, 0x00,              // TYPE COVERED = 0 (SIG(0))
+            0x0F,                    // ALGORITHM    = Ed25519
+            0x00,                    // LABELS
+            0x00, 0x00, 0x00, 0x00,  // ORIGINAL TTL
+            0x00, 0x00, 0x01, 0x2C,  // EXPIRATION
+            0x00, 0x00, 0x00, 0x00,  // INCEPTION
+            0xAB, 0xCD,              // KEY TAG
+            0x01, b'k', 0x00,        // SIGNER'S NAME = k.
+            0x01, 0x02, 0x03,        // SIGNATURE
+        ];
+        let sig = Sig::read_from(&mut Reader::new(buffer), buffer.len() as u16).unwrap();
+        assert_eq!(sig, Sig {
+            type_covered: rtype::Type::Unknown(0),
+            algorithm:    Algorithm::Ed25519,
+            labels:       0,
+            original_ttl: 0,
+            expiration:   300,
+            inception:    0,
+            key_tag:      0xABCD,
+            signer_name:  "k.".parse().unwrap(),
+            signature:    vec![0x01, 0x02, 0x03],
+        });
+        let mut encoded = vec![];
+        sig.write_to(&mut encoded);
+        assert_eq!(encoded, buffer);
+    }
+}
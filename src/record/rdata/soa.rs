@@ -0,0 +1,80 @@
+use crate::reader::Reader;
+use crate::error::{DecodeError, Field};
+use crate::name::Name;
+use crate::record::rtype;
+use crate::sink::Sink;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Soa {
+    pub mname:   Name,
+    pub rname:   Name,
+    pub serial:  u32,
+    pub refresh: u32,
+    pub retry:   u32,
+    pub expire:  u32,
+    pub minimum: u32,
+}
+
+impl Soa {
+    pub(crate) fn read_from(buffer: &mut Reader) -> Result<Self, DecodeError> {
+        let field = Field::RData(rtype::Type::Soa);
+        Ok(Self {
+            mname:   Name::read_from(buffer)?,
+            rname:   Name::read_from(buffer)?,
+            serial:  buffer.read_u32_field(field)?,
+            refresh: buffer.read_u32_field(field)?,
+            retry:   buffer.read_u32_field(field)?,
+            expire:  buffer.read_u32_field(field)?,
+            minimum: buffer.read_u32_field(field)?,
+        })
+    }
+
+    pub(crate) fn write_to(&self, buffer: &mut impl Sink) {
+        self.mname.write_to(buffer);
+        self.rname.write_to(buffer);
+        for value in [self.serial, self.refresh, self.retry, self.expire, self.minimum] {
+            buffer.put_slice(&value.to_be_bytes());
+        }
+    }
+}
+
+impl core::fmt::Display for Soa {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f, "{} {} {} {} {} {} {}",
+            self.mname, self.rname, self.serial, self.refresh, self.retry, self.expire, self.minimum,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_reads_and_writes_soa() {
+        let buffer: &[u8] = &[
+            0x02, b'n', b's', 0x00,    // MNAME   = ns.
+            0x02, b'h', b'm', 0x00,    // RNAME   = hm.
+            0x00, 0x00, 0x00, 0x01,    // SERIAL  = 1
+            0x00, 0x00, 0x0E, 0x10,    // REFRESH = 3600
+            0x00, 0x00, 0x02, 0x58,    // RETRY   = 600
+            0x00, 0x09, 0x3A, 0x80,    // EXPIRE  = 604800
+            0x00, 0x00, 0x01, 0x2C,    // MINIMUM = 300
+        ];
+        let soa = Soa::read_from(&mut Reader::new(buffer)).unwrap();
+        assert_eq!(soa, Soa {
+            mname:   "ns.".parse().unwrap(),
+            rname:   "hm.".parse().unwrap(),
+            serial:  1,
+            refresh: 3600,
+            retry:   600,
+            expire:  604800,
+            minimum: 300,
+        });
+        let mut encoded = vec![];
+        soa.write_to(&mut encoded);
+        assert_eq!(encoded, buffer);
+    }
+}
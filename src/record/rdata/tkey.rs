@@ -0,0 +1,122 @@
+use alloc::vec::Vec;
+use crate::reader::Reader;
+use crate::error::{DecodeError, EncodeError, Field};
+use crate::header::rcode;
+use crate::name::Name;
+use crate::presentation;
+use crate::record::rtype;
+use crate::sink::Sink;
+
+pub mod mode;
+
+/// TKEY RDATA (RFC 2930 §2).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tkey {
+    pub algorithm:  Name,
+    pub inception:  u32,
+    pub expiration: u32,
+    pub mode:       mode::Mode,
+    pub error:      rcode::Rcode,
+    pub key:        Vec<u8>,
+    pub other:      Vec<u8>,
+}
+
+impl Tkey {
+    pub(crate) fn read_from(buffer: &mut Reader) -> Result<Self, DecodeError> {
+        let field = Field::RData(rtype::Type::Tkey);
+        let algorithm = Name::read_from(buffer)?;
+        let inception = buffer.read_u32_field(field)?;
+        let expiration = buffer.read_u32_field(field)?;
+        let mode = mode::Mode::from(buffer.read_u16_field(field)?);
+        let error = buffer.read_u16_field(field)?;
+        let error = rcode::Rcode::try_from(error).unwrap_or(rcode::Rcode::Unknown(error));
+        let key_size = buffer.read_u16_field(field)?;
+        let key = buffer.read_bytes_field(key_size as usize, field)?;
+        let other_size = buffer.read_u16_field(field)?;
+        let other = buffer.read_bytes_field(other_size as usize, field)?;
+        Ok(Self { algorithm, inception, expiration, mode, error, key, other })
+    }
+
+    pub(crate) fn write_to(&self, buffer: &mut impl Sink) -> Result<(), EncodeError> {
+        let field = Field::RData(rtype::Type::Tkey);
+        self.algorithm.write_to(buffer);
+        buffer.put_slice(&self.inception.to_be_bytes());
+        buffer.put_slice(&self.expiration.to_be_bytes());
+        buffer.put_slice(&u16::from(self.mode).to_be_bytes());
+        buffer.put_slice(&Into::<u16>::into(self.error).to_be_bytes());
+        super::write_sized_bytes(buffer, &self.key, field)?;
+        super::write_sized_bytes(buffer, &self.other, field)
+    }
+}
+
+impl core::fmt::Display for Tkey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} {} {} {} {} ", self.algorithm, self.inception, self.expiration, u16::from(self.mode), self.error)?;
+        presentation::fmt_sized_base64(&self.key, f)?;
+        f.write_str(" ")?;
+        presentation::fmt_sized_base64(&self.other, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_reads_and_writes_tkey() {
+        let buffer: &[u8] = &[
+            0x01, b'a', 0x00,        // ALGORITHM  = a.
+            0x5F, 0x5E, 0x10, 0x00,  // INCEPTION
+            0x5F, 0x5E, 0x1E, 0x10,  // EXPIRATION
+            0x00, 0x02,              // MODE       = Diffie-Hellman exchange
+            0x00, 0x13,              // ERROR      = BADMODE
+            0x00, 0x02, 0x12, 0x34,  // KEY SIZE, KEY DATA
+            0x00, 0x00,              // OTHER SIZE
+        ];
+        let tkey = Tkey::read_from(&mut Reader::new(buffer)).unwrap();
+        assert_eq!(tkey, Tkey {
+            algorithm:  "a.".parse().unwrap(),
+            inception:  0x5F5E_1000,
+            expiration: 0x5F5E_1E10,
+            mode:       mode::Mode::DiffieHellmanExchange,
+            error:      rcode::Rcode::Badmode,
+            key:        vec![0x12, 0x34],
+            other:      vec![],
+        });
+        let mut encoded = vec![];
+        tkey.write_to(&mut encoded).unwrap();
+        assert_eq!(encoded, buffer);
+    }
+
+    #[test]
+    fn it_keeps_unassigned_error() {
+        let buffer: &[u8] = &[
+            0x00,                    // ALGORITHM  = .
+            0x00, 0x00, 0x00, 0x01,  // INCEPTION
+            0x00, 0x00, 0x00, 0x02,  // EXPIRATION
+            0x00, 0x03,              // MODE       = GSS-API negotiation
+            0x00, 0x0C,              // ERROR      = 12, unassigned
+            0x00, 0x00,              // KEY SIZE
+            0x00, 0x00,              // OTHER SIZE
+        ];
+        let tkey = Tkey::read_from(&mut Reader::new(buffer)).unwrap();
+        assert_eq!(tkey.error, rcode::Rcode::Unknown(12));
+        let mut encoded = vec![];
+        tkey.write_to(&mut encoded).unwrap();
+        assert_eq!(encoded, buffer);
+    }
+
+    #[test]
+    fn it_reports_error_when_key_data_lack() {
+        let buffer: &[u8] = &[
+            0x00,
+            0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00,
+            0x00, 0x01,
+            0x00, 0x00,
+            0x00, 0x04, 0x12, 0x34,  // KEY SIZE = 4 (!!!LACK!!!)
+        ];
+        assert!(Tkey::read_from(&mut Reader::new(buffer)).is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use alloc::{format, string::String};
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    ServerAssignment,
+    DiffieHellmanExchange,
+    GssApiNegotiation,
+    ResolverAssignment,
+    KeyDeletion,
+    Unassigned(u16),
+    Reserved(u16),
+}
+
+impl Mode {
+    pub fn to_hex_string(&self) -> String {
+        let value: u16 = (*self).into();
+        format!("{:04X}", value)
+    }
+
+    pub fn is_assigned(&self) -> bool {
+        !matches!(self, Self::Unassigned(_) | Self::Reserved(_))
+    }
+}
+
+impl core::convert::From<u16> for Mode {
+    fn from(value: u16) -> Self {
+        match value {
+            1         => Self::ServerAssignment     ,
+            2         => Self::DiffieHellmanExchange,
+            3         => Self::GssApiNegotiation    ,
+            4         => Self::ResolverAssignment   ,
+            5         => Self::KeyDeletion          ,
+            0 | 65535 => Self::Reserved(value)      ,
+            v         => Self::Unassigned(v)        ,
+        }
+    }
+}
+
+impl core::convert::From<Mode> for u16 {
+    fn from(value: Mode) -> Self {
+        match value {
+            Mode::ServerAssignment      => 1,
+            Mode::DiffieHellmanExchange => 2,
+            Mode::GssApiNegotiation     => 3,
+            Mode::ResolverAssignment    => 4,
+            Mode::KeyDeletion           => 5,
+            Mode::Unassigned(v)         => v,
+            Mode::Reserved(v)           => v,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_converts_to_raw_value() {
+        let value: u16 = Mode::ServerAssignment.into();
+        assert_eq!(value, 1);
+        let value: u16 = Mode::DiffieHellmanExchange.into();
+        assert_eq!(value, 2);
+        let value: u16 = Mode::GssApiNegotiation.into();
+        assert_eq!(value, 3);
+        let value: u16 = Mode::ResolverAssignment.into();
+        assert_eq!(value, 4);
+        let value: u16 = Mode::KeyDeletion.into();
+        assert_eq!(value, 5);
+    }
+
+    #[test]
+    fn it_converts_from_u16() {
+        assert_eq!(Mode::from(1_u16),     Mode::ServerAssignment     );
+        assert_eq!(Mode::from(2_u16),     Mode::DiffieHellmanExchange);
+        assert_eq!(Mode::from(5_u16),     Mode::KeyDeletion          );
+        assert_eq!(Mode::from(6_u16),     Mode::Unassigned(6)        );
+        assert_eq!(Mode::from(0_u16),     Mode::Reserved(0)          );
+        assert_eq!(Mode::from(65535_u16), Mode::Reserved(65535)      );
+    }
+
+    #[test]
+    fn it_reports_whether_assigned() {
+        assert!(Mode::KeyDeletion.is_assigned());
+        assert!(!Mode::Unassigned(6).is_assigned());
+        assert!(!Mode::Reserved(0).is_assigned());
+    }
+
+    #[test]
+    fn it_converts_to_hex_string() {
+        assert_eq!(Mode::DiffieHellmanExchange.to_hex_string(), "0002");
+        assert_eq!(Mode::Reserved(65535).to_hex_string(),       "FFFF");
+    }
+}
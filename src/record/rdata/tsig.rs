@@ -0,0 +1,114 @@
+use alloc::vec::Vec;
+use crate::reader::Reader;
+use crate::error::{self, DecodeError, EncodeError, Field};
+use crate::header::rcode;
+use crate::name::Name;
+use crate::presentation;
+use crate::record::rtype;
+use crate::sink::Sink;
+
+pub(crate) const MAX_TIME_SIGNED: u64 = 0xFFFF_FFFF_FFFF;
+
+/// TSIG RDATA (RFC 8945 §4.2).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tsig {
+    pub algorithm:   Name,
+    /// Seconds since the epoch, 48 bits on the wire.
+    pub time_signed: u64,
+    pub fudge:       u16,
+    pub mac:         Vec<u8>,
+    pub original_id: u16,
+    pub error:       rcode::Rcode,
+    pub other:       Vec<u8>,
+}
+
+impl Tsig {
+    pub(crate) fn read_from(buffer: &mut Reader) -> Result<Self, DecodeError> {
+        let field = Field::RData(rtype::Type::Tsig);
+        let algorithm = Name::read_from(buffer)?;
+        let time_signed = buffer.read_bytes_field(6, field)?.iter().fold(0, |time, &octet| (time << 8) | octet as u64);
+        let fudge = buffer.read_u16_field(field)?;
+        let mac_size = buffer.read_u16_field(field)?;
+        let mac = buffer.read_bytes_field(mac_size as usize, field)?;
+        let original_id = buffer.read_u16_field(field)?;
+        let error = buffer.read_u16_field(field)?;
+        let error = rcode::Rcode::try_from(error).unwrap_or(rcode::Rcode::Unknown(error));
+        let other_size = buffer.read_u16_field(field)?;
+        let other = buffer.read_bytes_field(other_size as usize, field)?;
+        Ok(Self { algorithm, time_signed, fudge, mac, original_id, error, other })
+    }
+
+    pub(crate) fn write_to(&self, buffer: &mut impl Sink) -> Result<(), EncodeError> {
+        let field = Field::RData(rtype::Type::Tsig);
+        self.algorithm.write_to(buffer);
+        if self.time_signed > MAX_TIME_SIGNED {
+            return Err(error::value_too_large(field, buffer.written(), self.time_signed));
+        }
+        buffer.put_slice(&self.time_signed.to_be_bytes()[2..]);
+        buffer.put_slice(&self.fudge.to_be_bytes());
+        super::write_sized_bytes(buffer, &self.mac, field)?;
+        buffer.put_slice(&self.original_id.to_be_bytes());
+        buffer.put_slice(&Into::<u16>::into(self.error).to_be_bytes());
+        super::write_sized_bytes(buffer, &self.other, field)
+    }
+}
+
+/// Printed like dig does: algorithm, time signed, fudge, MAC, original ID, error and other data.
+impl core::fmt::Display for Tsig {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} {} {} ", self.algorithm, self.time_signed, self.fudge)?;
+        presentation::fmt_sized_base64(&self.mac, f)?;
+        write!(f, " {} {} ", self.original_id, self.error)?;
+        presentation::fmt_sized_base64(&self.other, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_reads_and_writes_tsig() {
+        let buffer: &[u8] = &[
+            0x01, b'h', 0x00,                    // ALGORITHM   = h.
+            0x00, 0x00, 0x5F, 0x5E, 0x10, 0x00,  // TIME SIGNED
+            0x01, 0x2C,                          // FUDGE       = 300
+            0x00, 0x02, 0xAB, 0xCD,              // MAC SIZE, MAC
+            0x12, 0x34,                          // ORIGINAL ID
+            0x00, 0x12,                          // ERROR       = BADTIME
+            0x00, 0x00,                          // OTHER LEN
+        ];
+        let tsig = Tsig::read_from(&mut Reader::new(buffer)).unwrap();
+        assert_eq!(tsig, Tsig {
+            algorithm:   "h.".parse().unwrap(),
+            time_signed: 0x5F5E_1000,
+            fudge:       300,
+            mac:         vec![0xAB, 0xCD],
+            original_id: 0x1234,
+            error:       rcode::Rcode::Badtime,
+            other:       vec![],
+        });
+        let mut encoded = vec![];
+        tsig.write_to(&mut encoded).unwrap();
+        assert_eq!(encoded, buffer);
+    }
+
+    #[test]
+    fn it_keeps_unassigned_error() {
+        let buffer: &[u8] = &[
+            0x00,                                // ALGORITHM   = .
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x01,  // TIME SIGNED
+            0x01, 0x2C,                          // FUDGE       = 300
+            0x00, 0x00,                          // MAC SIZE
+            0x12, 0x34,                          // ORIGINAL ID
+            0x00, 0x0C,                          // ERROR       = 12, unassigned
+            0x00, 0x00,                          // OTHER LEN
+        ];
+        let tsig = Tsig::read_from(&mut Reader::new(buffer)).unwrap();
+        assert_eq!(tsig.error, rcode::Rcode::Unknown(12));
+        let mut encoded = vec![];
+        tsig.write_to(&mut encoded).unwrap();
+        assert_eq!(encoded, buffer);
+    }
+}
@@ -0,0 +1,165 @@
+use alloc::{format, string::String};
+use crate::error::UnknownMnemonic;
+use crate::presentation;
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Type {
+    A,
+    Ns,
+    Cname,
+    Soa,
+    Ptr,
+    Mx,
+    Txt,
+    Sig,
+    Key,
+    Aaaa,
+    Opt,
+    Tkey,
+    Tsig,
+    Ixfr,
+    Axfr,
+    Any,
+    Unknown(u16),
+}
+
+impl Type {
+    pub fn to_hex_string(&self) -> String {
+        let value: u16 = (*self).into();
+        format!("{:04X}", value)
+    }
+}
+
+/// Mnemonics of RFC 1035 §3.2.2 and the RFCs defining the later types.
+const MNEMONICS: [(&str, Type); 16] = [
+    ("A",     Type::A    ),
+    ("NS",    Type::Ns   ),
+    ("CNAME", Type::Cname),
+    ("SOA",   Type::Soa  ),
+    ("PTR",   Type::Ptr  ),
+    ("MX",    Type::Mx   ),
+    ("TXT",   Type::Txt  ),
+    ("SIG",   Type::Sig  ),
+    ("KEY",   Type::Key  ),
+    ("AAAA",  Type::Aaaa ),
+    ("OPT",   Type::Opt  ),
+    ("TKEY",  Type::Tkey ),
+    ("TSIG",  Type::Tsig ),
+    ("IXFR",  Type::Ixfr ),
+    ("AXFR",  Type::Axfr ),
+    ("ANY",   Type::Any  ),
+];
+
+/// Mnemonic, or `TYPE` and the value for types without one (RFC 3597 §5).
+impl core::fmt::Display for Type {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match presentation::mnemonic_of(&MNEMONICS, self) {
+            Some(mnemonic) => f.write_str(mnemonic),
+            None           => write!(f, "TYPE{}", u16::from(*self)),
+        }
+    }
+}
+
+/// Accepts the mnemonics in any case, and `TYPE` followed by the value in decimal.
+impl core::str::FromStr for Type {
+    type Err = UnknownMnemonic;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        presentation::from_mnemonic(&MNEMONICS, s)
+            .or_else(|| presentation::parse_generic("TYPE", s).map(Self::from))
+            .ok_or(UnknownMnemonic { kind: "TYPE" })
+    }
+}
+
+impl core::convert::From<u16> for Type {
+    fn from(value: u16) -> Self {
+        match value {
+            1   => Self::A    ,
+            2   => Self::Ns   ,
+            5   => Self::Cname,
+            6   => Self::Soa  ,
+            12  => Self::Ptr  ,
+            15  => Self::Mx   ,
+            16  => Self::Txt  ,
+            24  => Self::Sig  ,
+            25  => Self::Key  ,
+            28  => Self::Aaaa ,
+            41  => Self::Opt  ,
+            249 => Self::Tkey ,
+            250 => Self::Tsig ,
+            251 => Self::Ixfr ,
+            252 => Self::Axfr ,
+            255 => Self::Any  ,
+            v   => Self::Unknown(v),
+        }
+    }
+}
+
+impl core::convert::From<Type> for u16 {
+    fn from(value: Type) -> Self {
+        match value {
+            Type::A          => 1,
+            Type::Ns         => 2,
+            Type::Cname      => 5,
+            Type::Soa        => 6,
+            Type::Ptr        => 12,
+            Type::Mx         => 15,
+            Type::Txt        => 16,
+            Type::Sig        => 24,
+            Type::Key        => 25,
+            Type::Aaaa       => 28,
+            Type::Opt        => 41,
+            Type::Tkey       => 249,
+            Type::Tsig       => 250,
+            Type::Ixfr       => 251,
+            Type::Axfr       => 252,
+            Type::Any        => 255,
+            Type::Unknown(v) => v,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_converts_to_raw_value() {
+        let value: u16 = Type::A.into();
+        assert_eq!(value, 1);
+        let value: u16 = Type::Tkey.into();
+        assert_eq!(value, 249);
+        let value: u16 = Type::Unknown(65280).into();
+        assert_eq!(value, 65280);
+    }
+
+    #[test]
+    fn it_converts_from_u16() {
+        assert_eq!(Type::from(1_u16),     Type::A              );
+        assert_eq!(Type::from(28_u16),    Type::Aaaa           );
+        assert_eq!(Type::from(249_u16),   Type::Tkey           );
+        assert_eq!(Type::from(65280_u16), Type::Unknown(65280) );
+    }
+
+    #[test]
+    fn it_displays_mnemonic() {
+        assert_eq!(Type::Aaaa.to_string(),           "AAAA"     );
+        assert_eq!(Type::Unknown(65280).to_string(), "TYPE65280");
+    }
+
+    #[test]
+    fn it_parses_mnemonic() {
+        assert_eq!("aaaa".parse(),          Ok(Type::Aaaa)                        );
+        assert_eq!("TYPE65280".parse(),     Ok(Type::Unknown(65280))              );
+        assert_eq!("type1".parse(),         Ok(Type::A)                           );
+        assert_eq!("TYPE".parse::<Type>(),  Err(UnknownMnemonic { kind: "TYPE" }));
+        assert_eq!("AAAAA".parse::<Type>(), Err(UnknownMnemonic { kind: "TYPE" }));
+    }
+
+    #[test]
+    fn it_converts_to_hex_string() {
+        assert_eq!(Type::Soa.to_hex_string(),  "0006");
+        assert_eq!(Type::Tkey.to_hex_string(), "00F9");
+    }
+}
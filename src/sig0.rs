@@ -0,0 +1,245 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+use crate::reader::Reader;
+use crate::error::{DecodeError, Section, SignError};
+use crate::header::{rcode, Header};
+use crate::message::Message;
+use crate::name::Name;
+use crate::question::Question;
+use crate::record::rdata::{algorithm::Algorithm, key::Key, sig::Sig, RData};
+use crate::record::{class, rtype, Record};
+
+const ARCOUNT_RANGE: core::ops::Range<usize> = 10..12;
+
+/// Private half of a KEY, implemented on top of whatever crypto library the application uses.
+pub trait Signer {
+    fn algorithm(&self) -> Algorithm;
+    fn key_tag(&self) -> u16;
+    fn sign(&self, data: &[u8]) -> Result<Vec<u8>, Box<dyn core::error::Error>>;
+}
+
+pub trait Verifier {
+    fn verify(&self, key: &Key, data: &[u8], signature: &[u8]) -> bool;
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum VerifyError {
+    Malformed(DecodeError),
+    Unsigned,
+    KeyMismatch,
+    OutOfValidity,
+    BadSignature,
+}
+
+impl VerifyError {
+    /// RCODE a server answers the failed request with.
+    pub fn rcode(&self) -> rcode::Rcode {
+        match self {
+            Self::Malformed(_) | Self::Unsigned => rcode::Rcode::FormatError,
+            Self::KeyMismatch                   => rcode::Rcode::Badkey,
+            Self::OutOfValidity                 => rcode::Rcode::Badtime,
+            Self::BadSignature                  => rcode::Rcode::BadversOrBadsig,
+        }
+    }
+}
+
+impl core::fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Malformed(cause) => write!(f, "cannot read signed message: {}", cause),
+            Self::Unsigned         => write!(f, "message does not end with a SIG(0) RR"),
+            Self::KeyMismatch      => write!(f, "SIG(0) was not made with the given key"),
+            Self::OutOfValidity    => write!(f, "SIG(0) is outside of its validity period"),
+            Self::BadSignature     => write!(f, "SIG(0) signature does not match"),
+        }
+    }
+}
+
+impl core::error::Error for VerifyError {}
+
+/// Appends a SIG(0) RR covering `message` as it is encoded at this point (RFC 2931 §3).
+pub fn sign(
+    message: &mut Message,
+    signer_name: Name,
+    signer: &dyn Signer,
+    inception: u32,
+    expiration: u32,
+) -> Result<(), SignError> {
+    let mut sig = Sig {
+        type_covered: rtype::Type::Unknown(0),
+        algorithm:    signer.algorithm(),
+        labels:       0,
+        original_ttl: 0,
+        expiration,
+        inception,
+        key_tag:      signer.key_tag(),
+        signer_name,
+        signature:    vec![],
+    };
+    let mut data = vec![];
+    sig.write_unsigned_to(&mut data);
+    let encoded: Vec<u8> = message.clone().try_into()?;
+    data.extend_from_slice(&encoded);
+    sig.signature = signer.sign(&data).map_err(SignError::Signer)?;
+    message.additionals.push(Record {
+        name:  Name::root(),
+        rtype: rtype::Type::Sig,
+        class: class::Class::Any,
+        ttl:   0,
+        rdata: RData::Sig(sig),
+    });
+    Ok(())
+}
+
+/// Verifies the SIG(0) RR that ends the wire-format message `buffer` against `key`,
+/// with `now` in seconds since the epoch.
+pub fn verify(buffer: &[u8], key: &Key, verifier: &dyn Verifier, now: u32) -> Result<Sig, VerifyError> {
+    let header = Header::try_from(buffer).map_err(VerifyError::Malformed)?;
+    let arcount: u16 = header.arcount.into();
+    if arcount == 0 {
+        return Err(VerifyError::Unsigned);
+    }
+    let an_pr_count: u16 = header.an_pr_count.into();
+    let ns_up_count: u16 = header.ns_up_count.into();
+    let mut cursor = Reader::new(buffer);
+    cursor.set_position(ARCOUNT_RANGE.end);
+    for _ in 0..header.qd_zo_count.into() {
+        Question::read_from(&mut cursor).map_err(|e| VerifyError::Malformed(e.in_section(Section::Question)))?;
+    }
+    let sections = [
+        (Section::Answer,     an_pr_count),
+        (Section::Authority,  ns_up_count),
+        (Section::Additional, arcount - 1),
+    ];
+    for (section, count) in sections {
+        for _ in 0..count {
+            Record::read_from(&mut cursor).map_err(|e| VerifyError::Malformed(e.in_section(section)))?;
+        }
+    }
+    let signed_length = cursor.position();
+    let record = Record::read_from(&mut cursor).map_err(|e| VerifyError::Malformed(e.in_section(Section::Additional)))?;
+    if cursor.position() != buffer.len() {
+        let offset = cursor.position();
+        return Err(VerifyError::Malformed(DecodeError::TrailingBytes { section: Section::Additional, offset }));
+    }
+    let sig = match record.rdata {
+        RData::Sig(sig) if record.name.is_root() && sig.type_covered == rtype::Type::Unknown(0) => sig,
+        _ => return Err(VerifyError::Unsigned),
+    };
+    if sig.algorithm != key.algorithm || sig.key_tag != key.key_tag() {
+        return Err(VerifyError::KeyMismatch);
+    }
+    // serial number arithmetic, so that validity periods may span the 2106 wrap-around
+    if (now.wrapping_sub(sig.inception) as i32) < 0 || (sig.expiration.wrapping_sub(now) as i32) < 0 {
+        return Err(VerifyError::OutOfValidity);
+    }
+
+    let mut data = vec![];
+    sig.write_unsigned_to(&mut data);
+    let start = data.len();
+    data.extend_from_slice(&buffer[..signed_length]);
+    data[start + ARCOUNT_RANGE.start..start + ARCOUNT_RANGE.end].copy_from_slice(&(arcount - 1).to_be_bytes());
+    if !verifier.verify(key, &data, &sig.signature) {
+        return Err(VerifyError::BadSignature);
+    }
+    Ok(sig)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::{id, opcode};
+    use crate::record::rdata::key;
+    use pretty_assertions::assert_eq;
+
+    // "signs" by hashing the data together with the public key, which is enough to tell tampering apart
+    struct MockKey(Key);
+
+    fn checksum(key: &Key, data: &[u8]) -> Vec<u8> {
+        let sum = data.iter().chain(&key.public_key).fold(0_u32, |sum, &octet| sum.wrapping_mul(31).wrapping_add(octet as u32));
+        sum.to_be_bytes().to_vec()
+    }
+
+    impl Signer for MockKey {
+        fn algorithm(&self) -> Algorithm {
+            self.0.algorithm
+        }
+
+        fn key_tag(&self) -> u16 {
+            self.0.key_tag()
+        }
+
+        fn sign(&self, data: &[u8]) -> Result<Vec<u8>, Box<dyn core::error::Error>> {
+            Ok(checksum(&self.0, data))
+        }
+    }
+
+    impl Verifier for MockKey {
+        fn verify(&self, key: &Key, data: &[u8], signature: &[u8]) -> bool {
+            checksum(key, data) == signature
+        }
+    }
+
+    fn key() -> Key {
+        Key {
+            flags:      0x0200,
+            protocol:   key::PROTOCOL_DNSSEC,
+            algorithm:  Algorithm::Ed25519,
+            public_key: vec![0x11; 32],
+        }
+    }
+
+    fn signed_message() -> Vec<u8> {
+        let mut message = Message {
+            header:      Header::query(id::Id(0x4242), opcode::Opcode::Update),
+            questions:   vec![],
+            answers:     vec![],
+            authorities: vec![],
+            additionals: vec![],
+        };
+        sign(&mut message, "client.example.".parse().unwrap(), &MockKey(key()), 1_000, 2_000).unwrap();
+        message.try_into().unwrap()
+    }
+
+    #[test]
+    fn it_verifies_signed_message() {
+        let buffer = signed_message();
+        let sig = verify(&buffer, &key(), &MockKey(key()), 1_500).unwrap();
+        assert_eq!(sig.signer_name, "client.example.".parse().unwrap());
+        assert_eq!(sig.key_tag, key().key_tag());
+    }
+
+    #[test]
+    fn it_reports_error_when_message_tampered() {
+        let mut buffer = signed_message();
+        buffer[1] ^= 0xFF;
+        assert_eq!(verify(&buffer, &key(), &MockKey(key()), 1_500), Err(VerifyError::BadSignature));
+    }
+
+    #[test]
+    fn it_reports_error_when_out_of_validity() {
+        let buffer = signed_message();
+        assert_eq!(verify(&buffer, &key(), &MockKey(key()), 999), Err(VerifyError::OutOfValidity));
+        assert_eq!(verify(&buffer, &key(), &MockKey(key()), 2_001), Err(VerifyError::OutOfValidity));
+        assert_eq!(VerifyError::OutOfValidity.rcode(), rcode::Rcode::Badtime);
+    }
+
+    #[test]
+    fn it_reports_error_when_key_differs() {
+        let buffer = signed_message();
+        let other = Key { public_key: vec![0x22; 32], ..key() };
+        assert_eq!(verify(&buffer, &other, &MockKey(other.clone()), 1_500), Err(VerifyError::KeyMismatch));
+    }
+
+    #[test]
+    fn it_reports_error_when_unsigned() {
+        let message = Message {
+            header:      Header::query(id::Id(0x4242), opcode::Opcode::Update),
+            questions:   vec![],
+            answers:     vec![],
+            authorities: vec![],
+            additionals: vec![],
+        };
+        let buffer: Vec<u8> = message.try_into().unwrap();
+        assert_eq!(verify(&buffer, &key(), &MockKey(key()), 1_500), Err(VerifyError::Unsigned));
+    }
+}
@@ -0,0 +1,147 @@
+use crate::error::EncodeError;
+
+/// Destination that encoders put wire-format bytes into.
+///
+/// A sink accepts every byte it is given; bounded sinks such as [`SliceSink`] keep counting
+/// past their capacity so that the caller can learn how much room was missing.
+pub trait Sink {
+    /// Number of bytes put so far.
+    fn written(&self) -> usize;
+
+    fn put_slice(&mut self, bytes: &[u8]);
+
+    fn put_u8(&mut self, value: u8) {
+        self.put_slice(&[value]);
+    }
+
+    /// Overwrites bytes already put at `at`, to fill in length fields once their content is known.
+    fn patch(&mut self, at: usize, bytes: &[u8]);
+}
+
+#[cfg(feature = "alloc")]
+impl Sink for alloc::vec::Vec<u8> {
+    fn written(&self) -> usize {
+        self.len()
+    }
+
+    fn put_slice(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+
+    fn patch(&mut self, at: usize, bytes: &[u8]) {
+        self[at..at + bytes.len()].copy_from_slice(bytes);
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Sink for bytes::BytesMut {
+    fn written(&self) -> usize {
+        self.len()
+    }
+
+    fn put_slice(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+
+    fn patch(&mut self, at: usize, bytes: &[u8]) {
+        self[at..at + bytes.len()].copy_from_slice(bytes);
+    }
+}
+
+/// Sink over a caller-provided buffer, such as a send buffer reused across responses.
+#[derive(Debug)]
+pub struct SliceSink<'a> {
+    buffer:  &'a mut [u8],
+    written: usize,
+}
+
+impl<'a> SliceSink<'a> {
+    pub fn new(buffer: &'a mut [u8]) -> Self {
+        Self { buffer, written: 0 }
+    }
+
+    /// Returns the number of bytes written, or [`EncodeError::BufferTooSmall`] when they did not fit.
+    pub fn finish(self) -> Result<usize, EncodeError> {
+        if self.written > self.buffer.len() {
+            return Err(EncodeError::BufferTooSmall { needed: self.written - self.buffer.len() });
+        }
+        Ok(self.written)
+    }
+}
+
+impl Sink for SliceSink<'_> {
+    fn written(&self) -> usize {
+        self.written
+    }
+
+    fn put_slice(&mut self, bytes: &[u8]) {
+        let at = self.written;
+        self.written += bytes.len();
+        self.patch(at, bytes);
+    }
+
+    fn patch(&mut self, at: usize, bytes: &[u8]) {
+        // bytes past the end are only counted, and reported by `finish`
+        if let Some(room) = self.buffer.get_mut(at..) {
+            let length = room.len().min(bytes.len());
+            room[..length].copy_from_slice(&bytes[..length]);
+        }
+    }
+}
+
+/// Sink that only counts, to learn the encoded size of a part before writing it.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default)]
+pub(crate) struct Counter {
+    written: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl Sink for Counter {
+    fn written(&self) -> usize {
+        self.written
+    }
+
+    fn put_slice(&mut self, bytes: &[u8]) {
+        self.written += bytes.len();
+    }
+
+    fn patch(&mut self, _: usize, _: &[u8]) {}
+}
+
+/// Runs `encode` over `buffer`, returning the number of bytes written.
+pub(crate) fn encode_into(
+    buffer: &mut [u8],
+    encode: impl FnOnce(&mut SliceSink) -> Result<usize, EncodeError>,
+) -> Result<usize, EncodeError> {
+    let mut sink = SliceSink::new(buffer);
+    encode(&mut sink)?;
+    sink.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_writes_into_slice() {
+        let mut buffer = [0; 4];
+        let mut sink = SliceSink::new(&mut buffer);
+        sink.put_slice(&[0x01, 0x02]);
+        sink.put_u8(0x03);
+        sink.patch(0, &[0xFF]);
+        assert_eq!(sink.finish(), Ok(3));
+        assert_eq!(buffer, [0xFF, 0x02, 0x03, 0x00]);
+    }
+
+    #[test]
+    fn it_reports_missing_room() {
+        let mut buffer = [0; 2];
+        let mut sink = SliceSink::new(&mut buffer);
+        sink.put_slice(&[0x01, 0x02, 0x03]);
+        sink.put_u8(0x04);
+        assert_eq!(sink.finish(), Err(EncodeError::BufferTooSmall { needed: 2 }));
+        assert_eq!(buffer, [0x01, 0x02]);
+    }
+}
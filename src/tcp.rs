@@ -0,0 +1,146 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use crate::error::{self, EncodeError, Field};
+use crate::sink::{self, Sink};
+
+pub(crate) const LENGTH_PREFIX: usize = 2;
+
+/// Splits a TCP or DoT byte stream into messages by their two-octet length prefix (RFC 1035 §4.2.2).
+///
+/// Bytes are pushed as they are read, however the reads happen to split or join messages.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default)]
+pub struct Framer {
+    buffer:   Vec<u8>,
+    /// Start of the bytes not handed out as a message yet.
+    consumed: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl Framer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends bytes read from the stream.
+    pub fn push(&mut self, bytes: &[u8]) {
+        // messages handed out before are no longer borrowed, so their room can be reused
+        self.buffer.drain(..self.consumed);
+        self.consumed = 0;
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Takes the next complete message without its length prefix, or `None` until more bytes are pushed.
+    pub fn next_message(&mut self) -> Option<&[u8]> {
+        let pending = &self.buffer[self.consumed..];
+        let length = u16::from_be_bytes([*pending.first()?, *pending.get(1)?]) as usize;
+        if pending.len() < LENGTH_PREFIX + length {
+            return None;
+        }
+        let start = self.consumed + LENGTH_PREFIX;
+        self.consumed = start + length;
+        Some(&self.buffer[start..self.consumed])
+    }
+
+    /// Number of bytes pushed that do not form a complete message yet.
+    pub fn pending(&self) -> usize {
+        self.buffer.len() - self.consumed
+    }
+}
+
+/// Writes the message `encode` puts into `sink` behind its length prefix, returning the number of bytes written.
+pub fn encode_to<S: Sink>(sink: &mut S, encode: impl FnOnce(&mut S) -> Result<usize, EncodeError>) -> Result<usize, EncodeError> {
+    let prefix_at = sink.written();
+    sink.put_slice(&[0; LENGTH_PREFIX]);
+    let length = encode(sink)?;
+    let prefix = u16::try_from(length).map_err(|_| error::too_long(Field::TcpLength, prefix_at, length))?;
+    sink.patch(prefix_at, &prefix.to_be_bytes());
+    Ok(LENGTH_PREFIX + length)
+}
+
+/// Like [`encode_to`], into a caller-provided buffer.
+pub fn encode_into<'a>(
+    buffer: &'a mut [u8],
+    encode: impl FnOnce(&mut sink::SliceSink<'a>) -> Result<usize, EncodeError>,
+) -> Result<usize, EncodeError> {
+    let mut sink = sink::SliceSink::new(buffer);
+    encode_to(&mut sink, encode)?;
+    sink.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::Header;
+    use pretty_assertions::assert_eq;
+
+    fn header_buffer() -> [u8; 12] {
+        [
+            0xAB, 0xCD, // ID      = 0xABCD
+            0x01, 0x00, // RD      = Recursion desired
+            0x00, 0x00, // QDCOUNT = 0
+            0x00, 0x00, // ANCOUNT = 0
+            0x00, 0x00, // NSCOUNT = 0
+            0x00, 0x00, // ARCOUNT = 0
+        ]
+    }
+
+    #[test]
+    fn it_encodes_with_length_prefix() {
+        let header = Header::try_from(header_buffer().as_slice()).unwrap();
+        let mut buffer = [0; 14];
+        assert_eq!(encode_into(&mut buffer, |sink| header.encode_to(sink)), Ok(14));
+        assert_eq!(&buffer[..2], &[0x00, 0x0C]);
+        assert_eq!(&buffer[2..], &header_buffer());
+
+        let mut buffer = [0; 13];
+        assert_eq!(encode_into(&mut buffer, |sink| header.encode_to(sink)), Err(EncodeError::BufferTooSmall { needed: 1 }));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn it_splits_partial_reads() {
+        let mut stream = vec![];
+        encode_to(&mut stream, |sink| {
+            sink.put_slice(&header_buffer());
+            Ok(12)
+        }).unwrap();
+        let mut framer = Framer::new();
+        for byte in &stream[..13] {
+            framer.push(&[*byte]);
+            assert_eq!(framer.next_message(), None);
+        }
+        framer.push(&stream[13..]);
+        assert_eq!(framer.next_message(), Some(&header_buffer()[..]));
+        assert_eq!(framer.next_message(), None);
+        assert_eq!(framer.pending(), 0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn it_splits_pipelined_messages() {
+        let mut framer = Framer::new();
+        framer.push(&[0x00, 0x01, 0xAA, 0x00, 0x02, 0xBB, 0xCC, 0x00]);
+        assert_eq!(framer.next_message(), Some(&[0xAA][..]));
+        assert_eq!(framer.next_message(), Some(&[0xBB, 0xCC][..]));
+        assert_eq!(framer.next_message(), None);
+        assert_eq!(framer.pending(), 1);
+        framer.push(&[0x00]);
+        assert_eq!(framer.next_message(), Some(&[][..]));
+    }
+
+    #[test]
+    fn it_reports_error_when_message_too_long() {
+        let mut buffer = [0; 4];
+        let encoded = encode_into(&mut buffer, |sink| {
+            sink.put_slice(&[0; 0x1_0000]);
+            Ok(0x1_0000)
+        });
+        assert_eq!(encoded, Err(EncodeError::TooLong {
+            section: error::Section::Header,
+            field:   Field::TcpLength,
+            offset:  0,
+            length:  0x1_0000,
+        }));
+    }
+}
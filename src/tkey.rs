@@ -0,0 +1,285 @@
+use alloc::{vec, vec::Vec};
+use crate::header::{id, one_bit_flag, opcode, rcode, Header};
+use crate::message::Message;
+use crate::name::Name;
+use crate::question::Question;
+use crate::record::rdata::{key::Key, tkey::{mode::Mode, Tkey}, RData};
+use crate::record::{class, rtype, Record};
+
+pub struct RequestBuilder {
+    id:         id::Id,
+    key_name:   Name,
+    algorithm:  Name,
+    inception:  u32,
+    expiration: u32,
+    nonce:      Vec<u8>,
+}
+
+impl RequestBuilder {
+    /// `algorithm` names the authentication algorithm the established key will be used with,
+    /// e.g. `hmac-sha256.`.
+    pub fn new(key_name: Name, algorithm: Name) -> Self {
+        Self {
+            id: id::Id(0),
+            key_name,
+            algorithm,
+            inception: 0,
+            expiration: 0,
+            nonce: vec![],
+        }
+    }
+
+    pub fn id(mut self, id: id::Id) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn validity(mut self, inception: u32, expiration: u32) -> Self {
+        self.inception = inception;
+        self.expiration = expiration;
+        self
+    }
+
+    /// Random key data mixed into the established key.
+    pub fn nonce(mut self, nonce: Vec<u8>) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    /// Request in which the server generates the key and encrypts it with `resolver_key`
+    /// (RFC 2930 §4.2).
+    pub fn server_assigned(self, owner: Name, resolver_key: Key) -> Message {
+        self.build(Mode::ServerAssignment, owner, resolver_key)
+    }
+
+    /// Request in which both sides derive the key from their Diffie-Hellman keys
+    /// (RFC 2930 §4.1).
+    pub fn diffie_hellman(self, owner: Name, resolver_key: Key) -> Message {
+        self.build(Mode::DiffieHellmanExchange, owner, resolver_key)
+    }
+
+    fn build(self, mode: Mode, owner: Name, resolver_key: Key) -> Message {
+        let tkey = Tkey {
+            algorithm:  self.algorithm,
+            inception:  self.inception,
+            expiration: self.expiration,
+            mode,
+            error:      rcode::Rcode::NoError,
+            key:        self.nonce,
+            other:      vec![],
+        };
+        Message {
+            header:      Header::query(self.id, opcode::Opcode::Query),
+            questions:   vec![Question {
+                name:   self.key_name.clone(),
+                qtype:  rtype::Type::Tkey,
+                qclass: class::Class::Any,
+            }],
+            answers:     vec![],
+            authorities: vec![],
+            additionals: vec![
+                tkey_record(self.key_name, tkey),
+                Record {
+                    name:  owner,
+                    rtype: rtype::Type::Key,
+                    class: class::Class::In,
+                    ttl:   0,
+                    rdata: RData::Key(resolver_key),
+                },
+            ],
+        }
+    }
+}
+
+fn tkey_record(key_name: Name, tkey: Tkey) -> Record {
+    Record {
+        name:  key_name,
+        rtype: rtype::Type::Tkey,
+        class: class::Class::Any,
+        ttl:   0,
+        rdata: RData::Tkey(tkey),
+    }
+}
+
+fn find_tkey<'a>(records: &'a [Record], key_name: &Name) -> Option<&'a Tkey> {
+    records.iter()
+        .filter(|record| &record.name == key_name)
+        .find_map(|record| match &record.rdata {
+            RData::Tkey(tkey) => Some(tkey),
+            _ => None,
+        })
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ResponseError {
+    NotResponse,
+    MissingTkey,
+    /// Either the header RCODE or the extended error carried in the TKEY RR.
+    Rejected(rcode::Rcode),
+}
+
+impl core::fmt::Display for ResponseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotResponse     => write!(f, "message is not a response"),
+            Self::MissingTkey     => write!(f, "response carries no TKEY RR for the key"),
+            Self::Rejected(rcode) => write!(f, "server rejected TKEY request: {:?}", rcode),
+        }
+    }
+}
+
+impl core::error::Error for ResponseError {}
+
+/// Extracts the TKEY RR the server answered with for `key_name`.
+pub fn read_response<'a>(response: &'a Message, key_name: &Name) -> Result<&'a Tkey, ResponseError> {
+    if response.header.qr != one_bit_flag::Qr::Response {
+        return Err(ResponseError::NotResponse);
+    }
+    if response.header.rcode != rcode::Rcode::NoError {
+        return Err(ResponseError::Rejected(response.header.rcode));
+    }
+    let tkey = find_tkey(&response.answers, key_name).ok_or(ResponseError::MissingTkey)?;
+    if tkey.error != rcode::Rcode::NoError {
+        return Err(ResponseError::Rejected(tkey.error));
+    }
+    Ok(tkey)
+}
+
+/// Checks an incoming TKEY request, returning the extended error to answer with
+/// through [`error_response`] when it cannot be served.
+pub fn validate_request<'a, KeyInUse>(
+    query: &'a Message,
+    modes: &[Mode],
+    algorithms: &[Name],
+    key_in_use: KeyInUse,
+) -> Result<&'a Tkey, rcode::Rcode>
+    where KeyInUse: Fn(&Name) -> bool
+{
+    let key_name = match query.questions.as_slice() {
+        [question] if question.qtype == rtype::Type::Tkey => &question.name,
+        _ => return Err(rcode::Rcode::FormatError),
+    };
+    let tkey = find_tkey(&query.additionals, key_name).ok_or(rcode::Rcode::FormatError)?;
+    if !modes.contains(&tkey.mode) {
+        return Err(rcode::Rcode::Badmode);
+    }
+    if !algorithms.contains(&tkey.algorithm) {
+        return Err(rcode::Rcode::Badalg);
+    }
+    if tkey.mode != Mode::KeyDeletion && key_in_use(key_name) {
+        return Err(rcode::Rcode::Badname);
+    }
+    Ok(tkey)
+}
+
+/// Answer to `query` that echoes its TKEY RR with `error` in the extended error field.
+/// The header RCODE stays NOERROR as RFC 2930 §2.6 requires.
+pub fn error_response(query: &Message, error: rcode::Rcode) -> Message {
+    let answers = query.questions.first()
+        .and_then(|question| {
+            find_tkey(&query.additionals, &question.name)
+                .map(|tkey| tkey_record(question.name.clone(), Tkey {
+                    error,
+                    key: vec![],
+                    other: vec![],
+                    ..tkey.clone()
+                }))
+        })
+        .into_iter()
+        .collect();
+    Message {
+        header:      Header {
+            qr:    one_bit_flag::Qr::Response,
+            rcode: rcode::Rcode::NoError,
+            ..query.header
+        },
+        questions:   query.questions.clone(),
+        answers,
+        authorities: vec![],
+        additionals: vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::rdata::{algorithm::Algorithm, key};
+    use pretty_assertions::assert_eq;
+
+    fn dh_key() -> Key {
+        Key {
+            flags:      0,
+            protocol:   key::PROTOCOL_DNSSEC,
+            algorithm:  Algorithm::DiffieHellman,
+            public_key: vec![0x01, 0x02, 0x03],
+        }
+    }
+
+    fn dh_request() -> Message {
+        RequestBuilder::new("k.example.".parse().unwrap(), "hmac-sha256.".parse().unwrap())
+            .id(id::Id(0x1234))
+            .validity(1_000, 2_000)
+            .nonce(vec![0xAA; 4])
+            .diffie_hellman("resolver.example.".parse().unwrap(), dh_key())
+    }
+
+    #[test]
+    fn it_builds_diffie_hellman_request() {
+        let request = dh_request();
+        assert_eq!(request.header.id, id::Id(0x1234));
+        assert_eq!(request.header.qr, one_bit_flag::Qr::Query);
+        assert_eq!(request.questions[0].qtype, rtype::Type::Tkey);
+        let tkey = find_tkey(&request.additionals, &"k.example.".parse().unwrap()).unwrap();
+        assert_eq!(tkey.mode, Mode::DiffieHellmanExchange);
+        assert_eq!(tkey.key, vec![0xAA; 4]);
+        assert_eq!(request.additionals[1].rdata, RData::Key(dh_key()));
+
+        let encoded: Vec<u8> = request.clone().try_into().unwrap();
+        let decoded = Message::try_from(encoded.as_slice()).unwrap();
+        assert_eq!(decoded.questions, request.questions);
+        assert_eq!(decoded.additionals, request.additionals);
+    }
+
+    #[test]
+    fn it_builds_server_assigned_request() {
+        let request = RequestBuilder::new("k.example.".parse().unwrap(), "hmac-sha256.".parse().unwrap())
+            .server_assigned("resolver.example.".parse().unwrap(), dh_key());
+        let tkey = find_tkey(&request.additionals, &"k.example.".parse().unwrap()).unwrap();
+        assert_eq!(tkey.mode, Mode::ServerAssignment);
+    }
+
+    #[test]
+    fn it_validates_request() {
+        let request = dh_request();
+        let algorithms = ["hmac-sha256.".parse().unwrap()];
+        let modes = [Mode::DiffieHellmanExchange];
+        assert!(validate_request(&request, &modes, &algorithms, |_| false).is_ok());
+        assert_eq!(validate_request(&request, &[Mode::ServerAssignment], &algorithms, |_| false), Err(rcode::Rcode::Badmode));
+        assert_eq!(validate_request(&request, &modes, &[], |_| false), Err(rcode::Rcode::Badalg));
+        assert_eq!(validate_request(&request, &modes, &algorithms, |_| true), Err(rcode::Rcode::Badname));
+    }
+
+    #[test]
+    fn it_interprets_error_response() {
+        let request = dh_request();
+        let key_name = "k.example.".parse().unwrap();
+        let response = error_response(&request, rcode::Rcode::Badalg);
+        assert_eq!(response.header.rcode, rcode::Rcode::NoError);
+
+        let encoded: Vec<u8> = response.try_into().unwrap();
+        let response = Message::try_from(encoded.as_slice()).unwrap();
+        assert_eq!(read_response(&response, &key_name), Err(ResponseError::Rejected(rcode::Rcode::Badalg)));
+        assert_eq!(read_response(&request, &key_name), Err(ResponseError::NotResponse));
+    }
+
+    #[test]
+    fn it_reads_accepted_response() {
+        let request = dh_request();
+        let mut response = error_response(&request, rcode::Rcode::NoError);
+        if let RData::Tkey(tkey) = &mut response.answers[0].rdata {
+            tkey.key = vec![0x55; 4];
+        }
+        let tkey = read_response(&response, &"k.example.".parse().unwrap()).unwrap();
+        assert_eq!(tkey.key, vec![0x55; 4]);
+    }
+}
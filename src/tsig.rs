@@ -0,0 +1,148 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+use crate::error::{self, Field, SignError};
+use crate::header::rcode;
+use crate::message::Message;
+use crate::name::Name;
+use crate::record::rdata::{tsig::{Tsig, MAX_TIME_SIGNED}, RData};
+use crate::record::{class, rtype, Record};
+
+/// Shared secret of a TSIG key, implemented on top of whatever HMAC library the application uses.
+pub trait Signer {
+    /// Algorithm name such as `hmac-sha256.`.
+    fn algorithm(&self) -> Name;
+    fn sign(&self, data: &[u8]) -> Result<Vec<u8>, Box<dyn core::error::Error>>;
+}
+
+/// Appends a TSIG RR to a request (RFC 8945 §5.1), with `time_signed` in seconds since the epoch.
+pub fn sign(
+    message: &mut Message,
+    key_name: Name,
+    signer: &dyn Signer,
+    time_signed: u64,
+    fudge: u16,
+) -> Result<(), SignError> {
+    // the MAC would otherwise cover a truncated time; nothing is encoded yet, so no offset is known
+    if time_signed > MAX_TIME_SIGNED {
+        return Err(error::value_too_large(Field::RData(rtype::Type::Tsig), 0, time_signed).into());
+    }
+    let mut tsig = Tsig {
+        algorithm:   signer.algorithm(),
+        time_signed,
+        fudge,
+        mac:         vec![],
+        original_id: message.header.id.into(),
+        error:       rcode::Rcode::NoError,
+        other:       vec![],
+    };
+    let mut data: Vec<u8> = message.clone().try_into()?;
+    // TSIG variables (RFC 8945 §4.3.3)
+    key_name.to_lowercase().write_to(&mut data);
+    data.extend_from_slice(&u16::from(class::Class::Any).to_be_bytes());
+    data.extend_from_slice(&0_u32.to_be_bytes());
+    tsig.algorithm.to_lowercase().write_to(&mut data);
+    data.extend_from_slice(&tsig.time_signed.to_be_bytes()[2..]);
+    data.extend_from_slice(&tsig.fudge.to_be_bytes());
+    data.extend_from_slice(&Into::<u16>::into(tsig.error).to_be_bytes());
+    let other_len = u16::try_from(tsig.other.len())
+        .map_err(|_| error::too_long(Field::RData(rtype::Type::Tsig), data.len(), tsig.other.len()))?;
+    data.extend_from_slice(&other_len.to_be_bytes());
+    data.extend_from_slice(&tsig.other);
+    tsig.mac = signer.sign(&data).map_err(SignError::Signer)?;
+    message.additionals.push(Record {
+        name:  key_name,
+        rtype: rtype::Type::Tsig,
+        class: class::Class::Any,
+        ttl:   0,
+        rdata: RData::Tsig(tsig),
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::EncodeError;
+    use crate::header::{id, opcode, Header};
+    use pretty_assertions::assert_eq;
+
+    struct EchoSigner;
+
+    impl Signer for EchoSigner {
+        fn algorithm(&self) -> Name {
+            "hmac-sha256.".parse().unwrap()
+        }
+
+        fn sign(&self, data: &[u8]) -> Result<Vec<u8>, Box<dyn core::error::Error>> {
+            Ok(data.to_vec())
+        }
+    }
+
+    #[test]
+    fn it_appends_tsig_over_message_and_variables() {
+        let mut message = Message {
+            header:      Header::query(id::Id(0xBEEF), opcode::Opcode::Update),
+            questions:   vec![],
+            answers:     vec![],
+            authorities: vec![],
+            additionals: vec![],
+        };
+        let unsigned: Vec<u8> = message.clone().try_into().unwrap();
+        sign(&mut message, "Key.".parse().unwrap(), &EchoSigner, 0x0102_0304_0506, 300).unwrap();
+
+        let tsig = match &message.additionals[0].rdata {
+            RData::Tsig(tsig) => tsig.clone(),
+            rdata => panic!("unexpected RDATA: {:?}", rdata),
+        };
+        assert_eq!(tsig.original_id, 0xBEEF);
+        let mut expected = unsigned;
+        expected.extend_from_slice(b"\x03key\x00");
+        expected.extend_from_slice(&[0x00, 0xFF, 0x00, 0x00, 0x00, 0x00]);
+        expected.extend_from_slice(b"\x0bhmac-sha256\x00");
+        expected.extend_from_slice(&[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x01, 0x2C, 0x00, 0x00, 0x00, 0x00]);
+        assert_eq!(tsig.mac, expected);
+    }
+
+    #[test]
+    fn it_reports_error_when_signer_fails() {
+        struct FailingSigner;
+
+        impl Signer for FailingSigner {
+            fn algorithm(&self) -> Name {
+                "hmac-sha256.".parse().unwrap()
+            }
+
+            fn sign(&self, _: &[u8]) -> Result<Vec<u8>, Box<dyn core::error::Error>> {
+                Err("key is unavailable".into())
+            }
+        }
+
+        let mut message = Message {
+            header:      Header::query(id::Id(0xBEEF), opcode::Opcode::Update),
+            questions:   vec![],
+            answers:     vec![],
+            authorities: vec![],
+            additionals: vec![],
+        };
+        let error = sign(&mut message, "key.".parse().unwrap(), &FailingSigner, 0, 300).unwrap_err();
+        assert!(matches!(error, SignError::Signer(_)));
+        assert_eq!(error.to_string(), "signer failed: key is unavailable");
+        assert!(message.additionals.is_empty());
+    }
+
+    #[test]
+    fn it_reports_error_when_time_signed_too_large() {
+        let mut message = Message {
+            header:      Header::query(id::Id(0xBEEF), opcode::Opcode::Update),
+            questions:   vec![],
+            answers:     vec![],
+            authorities: vec![],
+            additionals: vec![],
+        };
+        let error = sign(&mut message, "key.".parse().unwrap(), &EchoSigner, 1 << 48, 300).unwrap_err();
+        assert!(matches!(
+            error,
+            SignError::Encode(EncodeError::ValueTooLarge { field: Field::RData(rtype::Type::Tsig), value: 0x1_0000_0000_0000, .. }),
+        ));
+        assert!(message.additionals.is_empty());
+    }
+}
@@ -0,0 +1,207 @@
+use core::ops::Deref;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use crate::error::{self, DecodeError, Field};
+use crate::header::{self, id, one_bit_flag, opcode, rcode, Header};
+#[cfg(feature = "alloc")]
+use crate::message::{builder::ResponseBuilder, Message};
+#[cfg(feature = "alloc")]
+use crate::question::Question;
+#[cfg(feature = "alloc")]
+use crate::record::Record;
+
+/// Gives access to the header of a message.
+pub trait AsHeader {
+    fn header(&self) -> &Header;
+    fn header_mut(&mut self) -> &mut Header;
+}
+
+impl AsHeader for Header {
+    fn header(&self) -> &Header {
+        self
+    }
+
+    fn header_mut(&mut self) -> &mut Header {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsHeader for Message {
+    fn header(&self) -> &Header {
+        &self.header
+    }
+
+    fn header_mut(&mut self) -> &mut Header {
+        &mut self.header
+    }
+}
+
+/// Header or message with QR cleared. Of the flags, only RD and CD can be set.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(test, derive(PartialEq, Eq))]
+pub struct Query<T>(T);
+
+/// Header or message with QR set. Of the flags, only AA, TC, RA, AD and RCODE can be set.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(test, derive(PartialEq, Eq))]
+pub struct Response<T>(T);
+
+macro_rules! implement_common {
+    ($typed:ident, $qr:path) => {
+        impl<T> $typed<T> {
+            pub fn into_inner(self) -> T {
+                self.0
+            }
+        }
+
+        impl<T> Deref for $typed<T> {
+            type Target = T;
+
+            fn deref(&self) -> &T {
+                &self.0
+            }
+        }
+
+        impl<T: AsHeader> $typed<T> {
+            /// Wraps `inner` after checking its QR bit.
+            fn checked(inner: T) -> Result<Self, DecodeError> {
+                if inner.header().qr != $qr {
+                    let qr: u8 = inner.header().qr.into();
+                    return Err(error::invalid_value(Field::Qr, header::FLAGS_OFFSET, qr as u32));
+                }
+                Ok(Self(inner))
+            }
+
+            pub fn set_id(&mut self, id: id::Id) {
+                self.0.header_mut().id = id;
+            }
+        }
+
+        impl core::convert::TryFrom<Header> for $typed<Header> {
+            type Error = DecodeError;
+
+            fn try_from(header: Header) -> Result<Self, Self::Error> {
+                Self::checked(header)
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl core::convert::TryFrom<Message> for $typed<Message> {
+            type Error = DecodeError;
+
+            fn try_from(message: Message) -> Result<Self, Self::Error> {
+                Self::checked(message)
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl $typed<Message> {
+            pub fn questions_mut(&mut self) -> &mut Vec<Question> {
+                &mut self.0.questions
+            }
+
+            pub fn answers_mut(&mut self) -> &mut Vec<Record> {
+                &mut self.0.answers
+            }
+
+            pub fn authorities_mut(&mut self) -> &mut Vec<Record> {
+                &mut self.0.authorities
+            }
+
+            pub fn additionals_mut(&mut self) -> &mut Vec<Record> {
+                &mut self.0.additionals
+            }
+        }
+    };
+}
+
+implement_common!(Query, one_bit_flag::Qr::Query);
+implement_common!(Response, one_bit_flag::Qr::Response);
+
+impl Query<Header> {
+    pub fn new(id: id::Id, opcode: opcode::Opcode) -> Self {
+        Self(Header::query(id, opcode))
+    }
+
+    /// Header of a response to this query, as [`Header::response`] builds it.
+    pub fn response(&self) -> Response<Header> {
+        Response(Header::response(&self.0))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Query<Message> {
+    pub fn respond(&self) -> ResponseBuilder {
+        ResponseBuilder::new(&self.0)
+    }
+}
+
+impl<T: AsHeader> Query<T> {
+    pub fn set_recursion_desired(&mut self, value: bool) {
+        self.0.header_mut().set_recursion_desired(value);
+    }
+
+    pub fn set_checking_disabled(&mut self, value: bool) {
+        self.0.header_mut().set_checking_disabled(value);
+    }
+}
+
+impl<T: AsHeader> Response<T> {
+    pub fn set_authoritative(&mut self, value: bool) {
+        self.0.header_mut().set_authoritative(value);
+    }
+
+    pub fn set_truncated(&mut self, value: bool) {
+        self.0.header_mut().set_truncated(value);
+    }
+
+    pub fn set_recursion_available(&mut self, value: bool) {
+        self.0.header_mut().set_recursion_available(value);
+    }
+
+    pub fn set_authentic_data(&mut self, value: bool) {
+        self.0.header_mut().set_authentic_data(value);
+    }
+
+    pub fn set_rcode(&mut self, rcode: rcode::Rcode) {
+        self.0.header_mut().rcode = rcode;
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<ResponseBuilder> for Response<Message> {
+    fn from(builder: ResponseBuilder) -> Self {
+        Self(builder.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn it_checks_qr_on_conversion() {
+        let header = Header::query(id::Id(0x1234), opcode::Opcode::Query);
+        assert!(Query::try_from(header).is_ok());
+        assert_eq!(
+            Response::try_from(header),
+            Err(DecodeError::InvalidValue { section: error::Section::Header, field: Field::Qr, offset: 2, value: 0 }),
+        );
+    }
+
+    #[test]
+    fn it_answers_query_with_response() {
+        let mut query = Query::new(id::Id(0x1234), opcode::Opcode::Query);
+        query.set_recursion_desired(true);
+        let mut response = query.response();
+        response.set_recursion_available(true);
+        response.set_rcode(rcode::Rcode::Refused);
+        assert!(response.is_response());
+        assert!(response.recursion_desired());
+        assert!(response.recursion_available());
+        assert_eq!(response.rcode, rcode::Rcode::Refused);
+        assert_eq!(response.id, query.id);
+    }
+}
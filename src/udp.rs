@@ -0,0 +1,162 @@
+use alloc::vec::Vec;
+use crate::error::EncodeError;
+use crate::header::one_bit_flag;
+use crate::message::Message;
+use crate::record::{rtype, Record};
+use crate::sink::{self, Counter, Sink};
+
+/// Largest message a requester without EDNS accepts over UDP (RFC 1035 §4.2.1).
+pub const MIN_PAYLOAD_SIZE: u16 = 512;
+
+/// Largest response `query` accepts over UDP: the payload size its OPT RR advertises in the CLASS field,
+/// or 512 without one. Smaller advertised sizes are treated as 512 (RFC 6891 §6.2.5).
+pub fn payload_size(query: &Message) -> u16 {
+    query.additionals.iter()
+        .find(|record| record.rtype == rtype::Type::Opt)
+        .map_or(MIN_PAYLOAD_SIZE, |opt| u16::from(opt.class).max(MIN_PAYLOAD_SIZE))
+}
+
+/// Drops records from `response` until it encodes into `max_size` bytes.
+///
+/// Whole RRsets go from the end of the additional section first, keeping any OPT RR, then from the
+/// ends of the authority and answer sections. Only dropping the latter sets TC, since additional
+/// data is not required in a response (RFC 2181 §9). TSIG or SIG(0) has to be added afterwards.
+pub fn truncate(response: &Message, max_size: usize) -> Result<Message, EncodeError> {
+    let mut size = encoded_size(response)?;
+    let mut truncated = response.clone();
+    if size <= max_size {
+        return Ok(truncated);
+    }
+    let is_opt = |record: &Record| record.rtype == rtype::Type::Opt;
+    size -= drop_rrsets(&mut truncated.additionals, size - max_size, is_opt)?;
+    if size > max_size {
+        truncated.header.tc = one_bit_flag::Tc::Truncated;
+        size -= drop_rrsets(&mut truncated.authorities, size - max_size, |_| false)?;
+    }
+    if size > max_size {
+        size -= drop_rrsets(&mut truncated.answers, size - max_size, |_| false)?;
+    }
+    if size > max_size {
+        return Err(EncodeError::BufferTooSmall { needed: size - max_size });
+    }
+    Ok(truncated)
+}
+
+/// Encodes `response` into `sink`, truncated to `max_size` bytes, returning the number of bytes written.
+pub fn encode_to(response: &Message, max_size: usize, sink: &mut impl Sink) -> Result<usize, EncodeError> {
+    truncate(response, max_size)?.encode_to(sink)
+}
+
+/// Like [`encode_to`], into a caller-provided buffer, also truncating to fit the buffer.
+pub fn encode_into(response: &Message, max_size: usize, buffer: &mut [u8]) -> Result<usize, EncodeError> {
+    let max_size = max_size.min(buffer.len());
+    sink::encode_into(buffer, |sink| encode_to(response, max_size, sink))
+}
+
+fn encoded_size(message: &Message) -> Result<usize, EncodeError> {
+    let mut counter = Counter::default();
+    message.encode_to(&mut counter)
+}
+
+fn record_size(record: &Record) -> Result<usize, EncodeError> {
+    let mut counter = Counter::default();
+    record.write_to(&mut counter)?;
+    Ok(counter.written())
+}
+
+/// Drops RRsets from the end of `records` until at least `excess` bytes are gone, never dropping
+/// records that `keep` selects. Returns the number of bytes dropped.
+fn drop_rrsets(records: &mut Vec<Record>, excess: usize, keep: impl Fn(&Record) -> bool) -> Result<usize, EncodeError> {
+    let mut dropped = 0;
+    while dropped < excess {
+        let Some(last) = records.iter().rev().find(|record| !keep(record)).cloned() else {
+            break;
+        };
+        let mut kept = Vec::with_capacity(records.len());
+        for record in records.drain(..) {
+            if record.name == last.name && record.rtype == last.rtype && record.class == last.class {
+                dropped += record_size(&record)?;
+            } else {
+                kept.push(record);
+            }
+        }
+        *records = kept;
+    }
+    Ok(dropped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::{id, opcode, Header};
+    use crate::question::Question;
+    use crate::record::class;
+    use crate::record::rdata::RData;
+    use pretty_assertions::assert_eq;
+
+    fn a(name: &str, last_octet: u8) -> Record {
+        Record {
+            name:  name.parse().unwrap(),
+            rtype: rtype::Type::A,
+            class: class::Class::In,
+            ttl:   300,
+            rdata: RData::A(core::net::Ipv4Addr::new(192, 0, 2, last_octet)),
+        }
+    }
+
+    fn opt(payload_size: u16) -> Record {
+        Record {
+            name:  crate::name::Name::root(),
+            rtype: rtype::Type::Opt,
+            class: class::Class::from(payload_size),
+            ttl:   0,
+            rdata: RData::Empty,
+        }
+    }
+
+    fn response(answers: usize, additionals: Vec<Record>) -> Message {
+        Message {
+            header:      Header::query(id::Id(1), opcode::Opcode::Query),
+            questions:   vec![Question { name: "www.example.".parse().unwrap(), qtype: rtype::Type::A, qclass: class::Class::In }],
+            answers:     (0..answers).map(|i| a("www.example.", i as u8)).collect(),
+            authorities: vec![],
+            additionals,
+        }
+    }
+
+    #[test]
+    fn it_takes_payload_size_from_opt() {
+        assert_eq!(payload_size(&response(0, vec![])), 512);
+        assert_eq!(payload_size(&response(0, vec![opt(1232)])), 1232);
+        assert_eq!(payload_size(&response(0, vec![opt(100)])), 512);
+    }
+
+    #[test]
+    fn it_keeps_response_that_fits() {
+        let response = response(2, vec![opt(1232)]);
+        let truncated = truncate(&response, 512).unwrap();
+        assert_eq!(truncated, response);
+        assert_eq!(truncated.header.tc, one_bit_flag::Tc::NotTruncated);
+    }
+
+    #[test]
+    fn it_drops_additional_data_without_tc() {
+        // header 12, question 17, the answer and ns1 A records 27 octets each as names are not compressed, OPT 11 octets
+        let response = response(1, vec![a("ns1.example.", 1), a("ns2.example.", 2), opt(1232)]);
+        let truncated = truncate(&response, 12 + 17 + 27 + 27 + 11).unwrap();
+        assert_eq!(truncated.additionals, vec![a("ns1.example.", 1), opt(1232)]);
+        assert_eq!(truncated.header.tc, one_bit_flag::Tc::NotTruncated);
+    }
+
+    #[test]
+    fn it_drops_whole_rrsets_and_sets_tc() {
+        let mut response = response(0, vec![opt(1232)]);
+        response.answers = vec![a("a.example.", 1), a("b.example.", 1), a("b.example.", 2)];
+        let mut buffer = [0; 512];
+        let length = encode_into(&response, 12 + 17 + 27 + 27, &mut buffer).unwrap();
+        let truncated = Message::try_from(&buffer[..length]).unwrap();
+        assert_eq!(truncated.header.tc, one_bit_flag::Tc::Truncated);
+        assert_eq!(truncated.answers, vec![a("a.example.", 1)]);
+        assert_eq!(truncated.additionals, vec![opt(1232)], "should keep OPT RR");
+    }
+}
@@ -0,0 +1,374 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+use crate::error::{DecodeError, EncodeError, Section};
+use crate::header::{opcode, Header};
+use crate::message::Message;
+use crate::name::Name;
+use crate::question::Question;
+use crate::record::rdata::RData;
+use crate::record::{class, rtype, Record};
+use crate::sink::{self, Sink};
+
+pub mod builder;
+pub mod engine;
+
+/// A message with `Opcode::Update`, whose sections are reinterpreted per RFC 2136 §2.
+#[derive(Debug, Clone)]
+#[cfg_attr(test, derive(PartialEq, Eq))]
+pub struct UpdateMessage {
+    pub header:        Header,
+    pub zone:          Zone,
+    pub prerequisites: Vec<Prerequisite>,
+    pub updates:       Vec<Update>,
+    pub additionals:   Vec<Record>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Zone {
+    pub name:  Name,
+    pub class: class::Class,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Prerequisite {
+    RrsetExists { name: Name, rtype: rtype::Type },
+    /// The RRset exists and contains exactly these records (value dependent).
+    RrsetExistsWithValue(Record),
+    RrsetDoesNotExist { name: Name, rtype: rtype::Type },
+    NameInUse { name: Name },
+    NameNotInUse { name: Name },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Update {
+    Add(Record),
+    DeleteRrset { name: Name, rtype: rtype::Type },
+    DeleteAll { name: Name },
+    DeleteRecord { name: Name, rtype: rtype::Type, rdata: RData },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FromError {
+    NotUpdate(opcode::Opcode),
+    ZoneCount(usize),
+    ZoneType(rtype::Type),
+    InvalidPrerequisite(Box<Record>),
+    InvalidUpdate(Box<Record>),
+}
+
+impl core::fmt::Display for FromError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotUpdate(opcode)           => write!(f, "found {:?} message instead of UPDATE", opcode),
+            Self::ZoneCount(count)            => write!(f, "zone section must contain exactly one entry: {}", count),
+            Self::ZoneType(rtype)             => write!(f, "zone section must have type SOA: {:?}", rtype),
+            Self::InvalidPrerequisite(record) => write!(f, "found invalid prerequisite: {:?}", record),
+            Self::InvalidUpdate(record)       => write!(f, "found invalid update: {:?}", record),
+        }
+    }
+}
+
+impl core::error::Error for FromError {}
+
+fn is_meta_type(rtype: rtype::Type) -> bool {
+    matches!(rtype, rtype::Type::Any | rtype::Type::Axfr | rtype::Type::Ixfr | rtype::Type::Unknown(253) | rtype::Type::Unknown(254))
+}
+
+fn empty_record(name: Name, rtype: rtype::Type, class: class::Class) -> Record {
+    Record { name, rtype, class, ttl: 0, rdata: RData::Empty }
+}
+
+impl Prerequisite {
+    /// Interprets a record from the prerequisite section (RFC 2136 §2.4, §3.2).
+    pub fn try_from_record(record: Record, zone_class: class::Class) -> Result<Self, FromError> {
+        if record.ttl != 0 {
+            return Err(FromError::InvalidPrerequisite(Box::new(record)));
+        }
+        match (record.class, record.rtype, &record.rdata) {
+            (class::Class::Any, rtype::Type::Any, RData::Empty)  => Ok(Self::NameInUse { name: record.name }),
+            (class::Class::Any, rtype, RData::Empty)             => Ok(Self::RrsetExists { name: record.name, rtype }),
+            (class::Class::None, rtype::Type::Any, RData::Empty) => Ok(Self::NameNotInUse { name: record.name }),
+            (class::Class::None, rtype, RData::Empty)            => Ok(Self::RrsetDoesNotExist { name: record.name, rtype }),
+            (class, rtype, _) if class == zone_class && !is_meta_type(rtype) => Ok(Self::RrsetExistsWithValue(record)),
+            _ => Err(FromError::InvalidPrerequisite(Box::new(record))),
+        }
+    }
+
+    pub fn to_record(&self, zone_class: class::Class) -> Record {
+        match self {
+            Self::RrsetExists { name, rtype }       => empty_record(name.clone(), *rtype, class::Class::Any),
+            Self::RrsetExistsWithValue(record)      => Record { class: zone_class, ttl: 0, ..record.clone() },
+            Self::RrsetDoesNotExist { name, rtype } => empty_record(name.clone(), *rtype, class::Class::None),
+            Self::NameInUse { name }                => empty_record(name.clone(), rtype::Type::Any, class::Class::Any),
+            Self::NameNotInUse { name }             => empty_record(name.clone(), rtype::Type::Any, class::Class::None),
+        }
+    }
+
+    pub fn name(&self) -> &Name {
+        match self {
+            Self::RrsetExists { name, .. } | Self::RrsetDoesNotExist { name, .. } => name,
+            Self::NameInUse { name } | Self::NameNotInUse { name }                => name,
+            Self::RrsetExistsWithValue(record)                                    => &record.name,
+        }
+    }
+}
+
+impl Update {
+    /// Interprets a record from the update section (RFC 2136 §2.5, §3.4.1).
+    pub fn try_from_record(record: Record, zone_class: class::Class) -> Result<Self, FromError> {
+        match (record.class, record.rtype, &record.rdata) {
+            (class::Class::Any, rtype::Type::Any, RData::Empty) if record.ttl == 0 => Ok(Self::DeleteAll { name: record.name }),
+            (class::Class::Any, rtype, RData::Empty) if record.ttl == 0 && !is_meta_type(rtype) => {
+                Ok(Self::DeleteRrset { name: record.name, rtype })
+            },
+            (class::Class::None, rtype, _) if record.ttl == 0 && !is_meta_type(rtype) => {
+                Ok(Self::DeleteRecord { name: record.name, rtype, rdata: record.rdata })
+            },
+            (class, rtype, _) if class == zone_class && !is_meta_type(rtype) => Ok(Self::Add(record)),
+            _ => Err(FromError::InvalidUpdate(Box::new(record))),
+        }
+    }
+
+    pub fn to_record(&self, zone_class: class::Class) -> Record {
+        match self {
+            Self::Add(record)                         => Record { class: zone_class, ..record.clone() },
+            Self::DeleteRrset { name, rtype }         => empty_record(name.clone(), *rtype, class::Class::Any),
+            Self::DeleteAll { name }                  => empty_record(name.clone(), rtype::Type::Any, class::Class::Any),
+            Self::DeleteRecord { name, rtype, rdata } => Record {
+                name:  name.clone(),
+                rtype: *rtype,
+                class: class::Class::None,
+                ttl:   0,
+                rdata: rdata.clone(),
+            },
+        }
+    }
+
+    pub fn name(&self) -> &Name {
+        match self {
+            Self::Add(record)                                         => &record.name,
+            Self::DeleteRrset { name, .. } | Self::DeleteAll { name } => name,
+            Self::DeleteRecord { name, .. }                           => name,
+        }
+    }
+}
+
+impl core::convert::TryFrom<Message> for UpdateMessage {
+    type Error = FromError;
+
+    fn try_from(value: Message) -> Result<Self, Self::Error> {
+        if value.header.opcode != opcode::Opcode::Update {
+            return Err(FromError::NotUpdate(value.header.opcode));
+        }
+        let zone = match value.questions.as_slice() {
+            [question] if question.qtype == rtype::Type::Soa => Zone {
+                name:  question.name.clone(),
+                class: question.qclass,
+            },
+            [question] => return Err(FromError::ZoneType(question.qtype)),
+            questions  => return Err(FromError::ZoneCount(questions.len())),
+        };
+        let prerequisites = value.answers.into_iter()
+            .map(|record| Prerequisite::try_from_record(record, zone.class))
+            .collect::<Result<Vec<_>, _>>()?;
+        let updates = value.authorities.into_iter()
+            .map(|record| Update::try_from_record(record, zone.class))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { header: value.header, zone, prerequisites, updates, additionals: value.additionals })
+    }
+}
+
+impl core::convert::From<UpdateMessage> for Message {
+    fn from(value: UpdateMessage) -> Self {
+        let zone_class = value.zone.class;
+        Message {
+            header:      value.header,
+            questions:   vec![Question {
+                name:   value.zone.name,
+                qtype:  rtype::Type::Soa,
+                qclass: zone_class,
+            }],
+            answers:     value.prerequisites.iter().map(|prerequisite| prerequisite.to_record(zone_class)).collect(),
+            authorities: value.updates.iter().map(|update| update.to_record(zone_class)).collect(),
+            additionals: value.additionals,
+        }
+    }
+}
+
+impl core::convert::From<FromError> for DecodeError {
+    fn from(value: FromError) -> Self {
+        let section = match value {
+            FromError::NotUpdate(_)                            => Section::Header,
+            FromError::ZoneCount(_) | FromError::ZoneType(_)   => Section::Question,
+            FromError::InvalidPrerequisite(_)                  => Section::Answer,
+            FromError::InvalidUpdate(_)                        => Section::Authority,
+        };
+        DecodeError::InvalidUpdate { section, cause: value }
+    }
+}
+
+impl core::convert::TryFrom<&[u8]> for UpdateMessage {
+    type Error = DecodeError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Ok(Self::try_from(Message::try_from(value)?)?)
+    }
+}
+
+impl UpdateMessage {
+    /// Encodes into `sink` with the counts taken from the sections, returning the number of bytes written.
+    pub fn encode_to(&self, sink: &mut impl Sink) -> Result<usize, EncodeError> {
+        Message::from(self.clone()).encode_to(sink)
+    }
+
+    /// Encodes into `buffer`, returning the number of bytes written.
+    pub fn encode_into(&self, buffer: &mut [u8]) -> Result<usize, EncodeError> {
+        sink::encode_into(buffer, |sink| self.encode_to(sink))
+    }
+}
+
+impl core::convert::TryInto<Vec<u8>> for UpdateMessage {
+    type Error = EncodeError;
+
+    fn try_into(self) -> Result<Vec<u8>, Self::Error> {
+        Message::from(self).try_into()
+    }
+}
+
+/// Presentation as dig prints UPDATE messages, with zone, prerequisite and update sections.
+impl core::fmt::Display for UpdateMessage {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", Message::from(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::{count, id};
+    use pretty_assertions::assert_eq;
+
+    fn record(name: &str, rtype: rtype::Type, class: class::Class, ttl: u32, rdata: RData) -> Record {
+        Record { name: name.parse().unwrap(), rtype, class, ttl, rdata }
+    }
+
+    fn a(address: [u8; 4]) -> RData {
+        RData::A(core::net::Ipv4Addr::from(address))
+    }
+
+    #[test]
+    fn it_decodes_from_u8_slice() {
+        let buffer: &[u8] = &[
+            0x12, 0x34,                   // ID = 0x1234
+            0b0010_1000, 0x00,            // QR = Query, OPCODE = Update
+            0x00, 0x01,                   // ZOCOUNT = 1
+            0x00, 0x01,                   // PRCOUNT = 1
+            0x00, 0x01,                   // UPCOUNT = 1
+            0x00, 0x00,                   // ARCOUNT = 0
+            0x01, b'e', 0x00,             // ZNAME   = e.
+            0x00, 0x06,                   // ZTYPE   = SOA
+            0x00, 0x01,                   // ZCLASS  = IN
+            0x01, b'a', 0xC0, 0x0C,       // NAME    = a.e.
+            0x00, 0xFF,                   // TYPE    = ANY
+            0x00, 0xFE,                   // CLASS   = NONE
+            0x00, 0x00, 0x00, 0x00,       // TTL     = 0
+            0x00, 0x00,                   // RDLENGTH = 0
+            0x01, b'b', 0xC0, 0x0C,       // NAME    = b.e.
+            0x00, 0x01,                   // TYPE    = A
+            0x00, 0x01,                   // CLASS   = IN
+            0x00, 0x00, 0x0E, 0x10,       // TTL     = 3600
+            0x00, 0x04,                   // RDLENGTH = 4
+            192, 0, 2, 1,                 // RDATA   = 192.0.2.1
+        ];
+        let update = UpdateMessage::try_from(buffer).unwrap();
+        assert_eq!(update.header.id, id::Id(0x1234));
+        assert_eq!(update.zone, Zone { name: "e.".parse().unwrap(), class: class::Class::In });
+        assert_eq!(update.prerequisites, vec![Prerequisite::NameNotInUse { name: "a.e.".parse().unwrap() }]);
+        assert_eq!(update.updates, vec![Update::Add(record("b.e.", rtype::Type::A, class::Class::In, 3600, a([192, 0, 2, 1])))]);
+    }
+
+    #[test]
+    fn it_interprets_prerequisites() {
+        let zone_class = class::Class::In;
+        let cases = [
+            (record("a.", rtype::Type::A,   class::Class::Any,  0, RData::Empty), Prerequisite::RrsetExists { name: "a.".parse().unwrap(), rtype: rtype::Type::A }),
+            (record("a.", rtype::Type::A,   class::Class::None, 0, RData::Empty), Prerequisite::RrsetDoesNotExist { name: "a.".parse().unwrap(), rtype: rtype::Type::A }),
+            (record("a.", rtype::Type::Any, class::Class::Any,  0, RData::Empty), Prerequisite::NameInUse { name: "a.".parse().unwrap() }),
+            (record("a.", rtype::Type::Any, class::Class::None, 0, RData::Empty), Prerequisite::NameNotInUse { name: "a.".parse().unwrap() }),
+            (record("a.", rtype::Type::A,   class::Class::In,   0, a([192, 0, 2, 1])), Prerequisite::RrsetExistsWithValue(record("a.", rtype::Type::A, class::Class::In, 0, a([192, 0, 2, 1])))),
+        ];
+        for (record, expected) in cases {
+            let prerequisite = Prerequisite::try_from_record(record.clone(), zone_class).unwrap();
+            assert_eq!(prerequisite, expected);
+            assert_eq!(prerequisite.to_record(zone_class), record);
+        }
+    }
+
+    #[test]
+    fn it_does_not_interpret_invalid_prerequisites() {
+        let zone_class = class::Class::In;
+        let nonzero_ttl = record("a.", rtype::Type::A, class::Class::Any, 1, RData::Empty);
+        assert_eq!(Prerequisite::try_from_record(nonzero_ttl.clone(), zone_class), Err(FromError::InvalidPrerequisite(Box::new(nonzero_ttl))));
+        let rdata_with_any = record("a.", rtype::Type::A, class::Class::Any, 0, a([192, 0, 2, 1]));
+        assert!(Prerequisite::try_from_record(rdata_with_any, zone_class).is_err());
+        let other_class = record("a.", rtype::Type::A, class::Class::Ch, 0, a([192, 0, 2, 1]));
+        assert!(Prerequisite::try_from_record(other_class, zone_class).is_err());
+    }
+
+    #[test]
+    fn it_interprets_updates() {
+        let zone_class = class::Class::In;
+        let cases = [
+            (record("a.", rtype::Type::A,   class::Class::In,   300, a([192, 0, 2, 1])), Update::Add(record("a.", rtype::Type::A, class::Class::In, 300, a([192, 0, 2, 1])))),
+            (record("a.", rtype::Type::A,   class::Class::Any,  0, RData::Empty), Update::DeleteRrset { name: "a.".parse().unwrap(), rtype: rtype::Type::A }),
+            (record("a.", rtype::Type::Any, class::Class::Any,  0, RData::Empty), Update::DeleteAll { name: "a.".parse().unwrap() }),
+            (record("a.", rtype::Type::A,   class::Class::None, 0, a([192, 0, 2, 1])), Update::DeleteRecord { name: "a.".parse().unwrap(), rtype: rtype::Type::A, rdata: a([192, 0, 2, 1]) }),
+        ];
+        for (record, expected) in cases {
+            let update = Update::try_from_record(record.clone(), zone_class).unwrap();
+            assert_eq!(update, expected);
+            assert_eq!(update.to_record(zone_class), record);
+        }
+    }
+
+    #[test]
+    fn it_does_not_interpret_invalid_updates() {
+        let zone_class = class::Class::In;
+        assert!(Update::try_from_record(record("a.", rtype::Type::Any, class::Class::In, 0, RData::Empty), zone_class).is_err());
+        assert!(Update::try_from_record(record("a.", rtype::Type::A, class::Class::Any, 300, RData::Empty), zone_class).is_err());
+        assert!(Update::try_from_record(record("a.", rtype::Type::Axfr, class::Class::Any, 0, RData::Empty), zone_class).is_err());
+        assert!(Update::try_from_record(record("a.", rtype::Type::A, class::Class::None, 300, a([192, 0, 2, 1])), zone_class).is_err());
+    }
+
+    #[test]
+    fn it_reports_error_when_not_update() {
+        let message = Message {
+            header:      Header::query(id::Id(0), opcode::Opcode::Query),
+            questions:   vec![],
+            answers:     vec![],
+            authorities: vec![],
+            additionals: vec![],
+        };
+        assert_eq!(UpdateMessage::try_from(message.clone()).unwrap_err(), FromError::NotUpdate(opcode::Opcode::Query));
+
+        let message = Message { header: Header::query(id::Id(0), opcode::Opcode::Update), ..message };
+        assert_eq!(UpdateMessage::try_from(message).unwrap_err(), FromError::ZoneCount(0));
+    }
+
+    #[test]
+    fn it_encodes_to_u8_array() {
+        let update = UpdateMessage {
+            header:        Header::query(id::Id(0x1234), opcode::Opcode::Update),
+            zone:          Zone { name: "e.".parse().unwrap(), class: class::Class::In },
+            prerequisites: vec![Prerequisite::RrsetExists { name: "a.e.".parse().unwrap(), rtype: rtype::Type::A }],
+            updates:       vec![Update::DeleteAll { name: "a.e.".parse().unwrap() }],
+            additionals:   vec![],
+        };
+        let encoded: Vec<u8> = update.clone().try_into().unwrap();
+        let decoded = UpdateMessage::try_from(encoded.as_slice()).unwrap();
+        assert_eq!(decoded.header.an_pr_count, count::AnPrCount(1));
+        assert_eq!(decoded.header.ns_up_count, count::NsUpCount(1));
+        assert_eq!(decoded.prerequisites, update.prerequisites);
+        assert_eq!(decoded.updates, update.updates);
+    }
+}
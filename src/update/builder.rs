@@ -0,0 +1,187 @@
+use alloc::{vec, vec::Vec};
+use crate::error::SignError;
+use crate::header::{id, opcode, Header};
+use crate::message::Message;
+use crate::name::Name;
+use crate::record::rdata::RData;
+use crate::record::{class, rtype, Record};
+use crate::tsig;
+use super::{Prerequisite, Update, UpdateMessage, Zone};
+
+struct TsigKey<'a> {
+    name:        Name,
+    signer:      &'a dyn tsig::Signer,
+    time_signed: u64,
+    fudge:       u16,
+}
+
+/// Assembles an UPDATE request the way `nsupdate` does: a zone, prerequisites,
+/// then additions and deletions, optionally signed with TSIG.
+pub struct UpdateBuilder<'a> {
+    id:            id::Id,
+    zone:          Zone,
+    prerequisites: Vec<Prerequisite>,
+    updates:       Vec<Update>,
+    tsig:          Option<TsigKey<'a>>,
+}
+
+impl<'a> UpdateBuilder<'a> {
+    pub fn new(zone: Name) -> Self {
+        Self {
+            id:            id::Id(0),
+            zone:          Zone { name: zone, class: class::Class::In },
+            prerequisites: vec![],
+            updates:       vec![],
+            tsig:          None,
+        }
+    }
+
+    pub fn id(mut self, id: id::Id) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn class(mut self, class: class::Class) -> Self {
+        self.zone.class = class;
+        self
+    }
+
+    pub fn require_rrset_exists(mut self, name: Name, rtype: rtype::Type) -> Self {
+        self.prerequisites.push(Prerequisite::RrsetExists { name, rtype });
+        self
+    }
+
+    /// Requires the RRset of `records` to consist of exactly these records.
+    pub fn require_rrset_exists_with_value(mut self, records: Vec<Record>) -> Self {
+        self.prerequisites.extend(records.into_iter().map(Prerequisite::RrsetExistsWithValue));
+        self
+    }
+
+    pub fn require_rrset_does_not_exist(mut self, name: Name, rtype: rtype::Type) -> Self {
+        self.prerequisites.push(Prerequisite::RrsetDoesNotExist { name, rtype });
+        self
+    }
+
+    pub fn require_name_in_use(mut self, name: Name) -> Self {
+        self.prerequisites.push(Prerequisite::NameInUse { name });
+        self
+    }
+
+    pub fn require_name_not_in_use(mut self, name: Name) -> Self {
+        self.prerequisites.push(Prerequisite::NameNotInUse { name });
+        self
+    }
+
+    pub fn add_record(mut self, record: Record) -> Self {
+        self.updates.push(Update::Add(record));
+        self
+    }
+
+    pub fn delete_rrset(mut self, name: Name, rtype: rtype::Type) -> Self {
+        self.updates.push(Update::DeleteRrset { name, rtype });
+        self
+    }
+
+    pub fn delete_all(mut self, name: Name) -> Self {
+        self.updates.push(Update::DeleteAll { name });
+        self
+    }
+
+    pub fn delete_record(mut self, name: Name, rtype: rtype::Type, rdata: RData) -> Self {
+        self.updates.push(Update::DeleteRecord { name, rtype, rdata });
+        self
+    }
+
+    /// Signs the request with TSIG, `time_signed` being seconds since the epoch.
+    pub fn tsig(mut self, key_name: Name, signer: &'a dyn tsig::Signer, time_signed: u64, fudge: u16) -> Self {
+        self.tsig = Some(TsigKey { name: key_name, signer, time_signed, fudge });
+        self
+    }
+
+    pub fn build_message(self) -> Result<UpdateMessage, SignError> {
+        let mut update = UpdateMessage {
+            header:        Header::query(self.id, opcode::Opcode::Update),
+            zone:          self.zone,
+            prerequisites: self.prerequisites,
+            updates:       self.updates,
+            additionals:   vec![],
+        };
+        if let Some(key) = self.tsig {
+            // TSIG covers the request as encoded, so it is signed in its message form
+            let mut message = Message::from(update.clone());
+            tsig::sign(&mut message, key.name, key.signer, key.time_signed, key.fudge)?;
+            update.additionals = message.additionals;
+        }
+        Ok(update)
+    }
+
+    /// Encodes the complete request, with the section counts taken from what was added.
+    pub fn build(self) -> Result<Vec<u8>, SignError> {
+        Ok(self.build_message()?.try_into()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::boxed::Box;
+    use crate::header::{count, one_bit_flag};
+    use pretty_assertions::assert_eq;
+
+    struct ZeroSigner;
+
+    impl tsig::Signer for ZeroSigner {
+        fn algorithm(&self) -> Name {
+            "hmac-sha256.".parse().unwrap()
+        }
+
+        fn sign(&self, _: &[u8]) -> Result<Vec<u8>, Box<dyn core::error::Error>> {
+            Ok(vec![0; 32])
+        }
+    }
+
+    fn a(name: &str, address: [u8; 4]) -> Record {
+        Record {
+            name:  name.parse().unwrap(),
+            rtype: rtype::Type::A,
+            class: class::Class::In,
+            ttl:   300,
+            rdata: RData::A(core::net::Ipv4Addr::from(address)),
+        }
+    }
+
+    #[test]
+    fn it_builds_update_request() {
+        let buffer = UpdateBuilder::new("example.".parse().unwrap())
+            .id(id::Id(0x0102))
+            .require_name_not_in_use("www.example.".parse().unwrap())
+            .add_record(a("www.example.", [192, 0, 2, 1]))
+            .add_record(a("www.example.", [192, 0, 2, 2]))
+            .delete_rrset("old.example.".parse().unwrap(), rtype::Type::A)
+            .build()
+            .unwrap();
+        let update = UpdateMessage::try_from(buffer.as_slice()).unwrap();
+        assert_eq!(update.header.id, id::Id(0x0102));
+        assert_eq!(update.header.qr, one_bit_flag::Qr::Query);
+        assert_eq!(update.header.opcode, opcode::Opcode::Update);
+        assert_eq!(update.header.qd_zo_count, count::QdZoCount(1));
+        assert_eq!(update.header.an_pr_count, count::AnPrCount(1));
+        assert_eq!(update.header.ns_up_count, count::NsUpCount(3));
+        assert_eq!(update.header.arcount, count::Arcount(0));
+        assert_eq!(update.zone.name, "example.".parse().unwrap());
+        assert_eq!(update.updates[2], Update::DeleteRrset { name: "old.example.".parse().unwrap(), rtype: rtype::Type::A });
+    }
+
+    #[test]
+    fn it_builds_signed_update_request() {
+        let signer = ZeroSigner;
+        let update = UpdateBuilder::new("example.".parse().unwrap())
+            .delete_all("www.example.".parse().unwrap())
+            .tsig("key.".parse().unwrap(), &signer, 1_700_000_000, 300)
+            .build_message()
+            .unwrap();
+        assert_eq!(update.additionals.len(), 1);
+        assert_eq!(update.additionals[0].rtype, rtype::Type::Tsig);
+        assert_eq!(update.additionals[0].name, "key.".parse().unwrap());
+    }
+}
@@ -0,0 +1,285 @@
+use alloc::vec::Vec;
+use alloc::collections::BTreeMap;
+use crate::header::rcode;
+use crate::name::Name;
+use crate::record::rdata::RData;
+use crate::record::{rtype, Record};
+use crate::zone;
+use super::{Prerequisite, Update, UpdateMessage};
+
+/// Evaluates the prerequisites of `message` against `zone` and applies its updates
+/// all-or-nothing (RFC 2136 §3), returning the RCODE to answer with.
+pub fn apply(zone: &mut zone::Zone, message: &UpdateMessage) -> rcode::Rcode {
+    apply_with_policy(zone, message, |_| true)
+}
+
+/// Same as [`apply`], but answers REFUSED unless `policy` permits every update (RFC 2136 §3.3).
+pub fn apply_with_policy<Policy>(zone: &mut zone::Zone, message: &UpdateMessage, policy: Policy) -> rcode::Rcode
+    where Policy: Fn(&Update) -> bool
+{
+    if &message.zone.name != zone.origin() || message.zone.class != zone.class() {
+        return rcode::Rcode::NotAuth;
+    }
+    if let Err(rcode) = check_prerequisites(zone, &message.prerequisites) {
+        return rcode;
+    }
+    if !message.updates.iter().all(policy) {
+        return rcode::Rcode::Refused;
+    }
+    // prescan, so that nothing is applied unless everything can be
+    if message.updates.iter().any(|update| !update.name().is_subdomain_of(zone.origin())) {
+        return rcode::Rcode::NotZone;
+    }
+
+    let serial = zone.soa().serial;
+    let mut changed = false;
+    for update in &message.updates {
+        changed |= apply_update(zone, update);
+    }
+    if changed && zone.soa().serial == serial {
+        zone.soa_mut().serial = serial.wrapping_add(1);
+    }
+    rcode::Rcode::NoError
+}
+
+fn check_prerequisites(zone: &zone::Zone, prerequisites: &[Prerequisite]) -> Result<(), rcode::Rcode> {
+    let mut expected_rrsets: BTreeMap<_, Vec<&RData>> = BTreeMap::new();
+    for prerequisite in prerequisites {
+        if !prerequisite.name().is_subdomain_of(zone.origin()) {
+            return Err(rcode::Rcode::NotZone);
+        }
+        match prerequisite {
+            Prerequisite::RrsetExists { name, rtype } => {
+                if zone.rrset(name, *rtype).next().is_none() {
+                    return Err(rcode::Rcode::NxRrset);
+                }
+            },
+            Prerequisite::RrsetDoesNotExist { name, rtype } => {
+                if zone.rrset(name, *rtype).next().is_some() {
+                    return Err(rcode::Rcode::YxRrset);
+                }
+            },
+            Prerequisite::NameInUse { name } => {
+                if !zone.contains_name(name) {
+                    return Err(rcode::Rcode::NameError);
+                }
+            },
+            Prerequisite::NameNotInUse { name } => {
+                if zone.contains_name(name) {
+                    return Err(rcode::Rcode::YxDomain);
+                }
+            },
+            Prerequisite::RrsetExistsWithValue(record) => {
+                let rtype: u16 = record.rtype.into();
+                expected_rrsets.entry((record.name.clone(), rtype)).or_default().push(&record.rdata);
+            },
+        }
+    }
+    // value dependent prerequisites compare whole RRsets (RFC 2136 §3.2.5)
+    for ((name, rtype), expected) in expected_rrsets {
+        let actual: Vec<&RData> = zone.rrset(&name, rtype.into()).map(|record| &record.rdata).collect();
+        if !actual.iter().all(|rdata| expected.contains(rdata)) || !expected.iter().all(|rdata| actual.contains(rdata)) {
+            return Err(rcode::Rcode::NxRrset);
+        }
+    }
+    Ok(())
+}
+
+fn is_newer_serial(serial: u32, than: u32) -> bool {
+    (serial.wrapping_sub(than) as i32) > 0
+}
+
+/// Applies a single update (RFC 2136 §3.4.2), returning whether the zone changed.
+fn apply_update(zone: &mut zone::Zone, update: &Update) -> bool {
+    let at_apex = update.name() == zone.origin();
+    match update {
+        Update::Add(record) => {
+            let record = Record { class: zone.class(), ..record.clone() };
+            let records = zone.records(&record.name);
+            let applicable = match record.rtype {
+                rtype::Type::Soa   => at_apex && matches!(&record.rdata, RData::Soa(soa) if is_newer_serial(soa.serial, zone.soa().serial)),
+                rtype::Type::Cname => records.iter().all(|existing| existing.rtype == rtype::Type::Cname),
+                _                  => records.iter().all(|existing| existing.rtype != rtype::Type::Cname),
+            };
+            if !applicable || records.contains(&record) {
+                return false;
+            }
+            if matches!(record.rtype, rtype::Type::Soa | rtype::Type::Cname) {
+                zone.remove_where(&record.name, |existing| existing.rtype == record.rtype);
+            }
+            zone.insert(record).is_ok()
+        },
+        Update::DeleteRrset { name, rtype } => {
+            if at_apex && matches!(rtype, rtype::Type::Soa | rtype::Type::Ns) {
+                return false;
+            }
+            remove(zone, name, |record| record.rtype == *rtype)
+        },
+        Update::DeleteAll { name } => {
+            remove(zone, name, |record| !at_apex || !matches!(record.rtype, rtype::Type::Soa | rtype::Type::Ns))
+        },
+        Update::DeleteRecord { name, rtype, rdata } => {
+            if *rtype == rtype::Type::Soa {
+                return false;
+            }
+            if at_apex && *rtype == rtype::Type::Ns && zone.rrset(name, rtype::Type::Ns).count() <= 1 {
+                return false;
+            }
+            remove(zone, name, |record| record.rtype == *rtype && &record.rdata == rdata)
+        },
+    }
+}
+
+fn remove<Predicate>(zone: &mut zone::Zone, name: &Name, predicate: Predicate) -> bool
+    where Predicate: Fn(&Record) -> bool
+{
+    let before = zone.records(name).len();
+    zone.remove_where(name, predicate);
+    zone.records(name).len() != before
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::id;
+    use crate::record::class;
+    use crate::record::rdata::soa::Soa;
+    use crate::update::builder::UpdateBuilder;
+    use pretty_assertions::assert_eq;
+
+    fn record(name: &str, rtype: rtype::Type, rdata: RData) -> Record {
+        Record { name: name.parse().unwrap(), rtype, class: class::Class::In, ttl: 300, rdata }
+    }
+
+    fn a(name: &str, last_octet: u8) -> Record {
+        record(name, rtype::Type::A, RData::A(core::net::Ipv4Addr::new(192, 0, 2, last_octet)))
+    }
+
+    fn ns(target: &str) -> Record {
+        record("example.", rtype::Type::Ns, RData::Ns(target.parse().unwrap()))
+    }
+
+    fn soa(serial: u32) -> Record {
+        record("example.", rtype::Type::Soa, RData::Soa(Soa {
+            mname:   "ns1.example.".parse().unwrap(),
+            rname:   "hostmaster.example.".parse().unwrap(),
+            serial,
+            refresh: 3600,
+            retry:   600,
+            expire:  604800,
+            minimum: 300,
+        }))
+    }
+
+    fn zone() -> zone::Zone {
+        let mut zone = zone::Zone::new(soa(10)).unwrap();
+        zone.insert(ns("ns1.example.")).unwrap();
+        zone.insert(a("www.example.", 1)).unwrap();
+        zone.insert(record("alias.example.", rtype::Type::Cname, RData::Cname("www.example.".parse().unwrap()))).unwrap();
+        zone
+    }
+
+    fn update() -> UpdateBuilder<'static> {
+        UpdateBuilder::new("example.".parse().unwrap()).id(id::Id(1))
+    }
+
+    #[test]
+    fn it_applies_updates_and_bumps_serial() {
+        let mut zone = zone();
+        let message = update()
+            .require_name_in_use("www.example.".parse().unwrap())
+            .require_rrset_exists_with_value(vec![a("www.example.", 1)])
+            .add_record(a("www.example.", 2))
+            .delete_record("www.example.".parse().unwrap(), rtype::Type::A, a("www.example.", 1).rdata)
+            .build_message()
+            .unwrap();
+        assert_eq!(apply(&mut zone, &message), rcode::Rcode::NoError);
+        let rrset: Vec<&Record> = zone.rrset(&"www.example.".parse().unwrap(), rtype::Type::A).collect();
+        assert_eq!(rrset, vec![&a("www.example.", 2)]);
+        assert_eq!(zone.soa().serial, 11);
+    }
+
+    #[test]
+    fn it_does_not_bump_serial_without_change() {
+        let mut zone = zone();
+        let message = update().add_record(a("www.example.", 1)).build_message().unwrap();
+        assert_eq!(apply(&mut zone, &message), rcode::Rcode::NoError);
+        assert_eq!(zone.soa().serial, 10);
+    }
+
+    #[test]
+    fn it_reports_failed_prerequisites() {
+        let cases = [
+            (update().require_name_in_use("nx.example.".parse().unwrap()),                      rcode::Rcode::NameError),
+            (update().require_name_not_in_use("www.example.".parse().unwrap()),                 rcode::Rcode::YxDomain),
+            (update().require_rrset_exists("www.example.".parse().unwrap(), rtype::Type::Aaaa), rcode::Rcode::NxRrset),
+            (update().require_rrset_does_not_exist("www.example.".parse().unwrap(), rtype::Type::A), rcode::Rcode::YxRrset),
+            (update().require_rrset_exists_with_value(vec![a("www.example.", 9)]),              rcode::Rcode::NxRrset),
+            (update().require_name_in_use("www.example.net.".parse().unwrap()),                 rcode::Rcode::NotZone),
+        ];
+        for (builder, expected) in cases {
+            let mut zone = zone();
+            let message = builder.add_record(a("new.example.", 1)).build_message().unwrap();
+            assert_eq!(apply(&mut zone, &message), expected);
+            assert!(!zone.contains_name(&"new.example.".parse().unwrap()), "should not apply any update");
+            assert_eq!(zone.soa().serial, 10);
+        }
+    }
+
+    #[test]
+    fn it_reports_out_of_zone_updates_before_applying() {
+        let mut zone = zone();
+        let message = update()
+            .add_record(a("new.example.", 1))
+            .add_record(a("www.example.net.", 1))
+            .build_message()
+            .unwrap();
+        assert_eq!(apply(&mut zone, &message), rcode::Rcode::NotZone);
+        assert!(!zone.contains_name(&"new.example.".parse().unwrap()));
+    }
+
+    #[test]
+    fn it_reports_not_auth_for_other_zone() {
+        let mut zone = zone();
+        let message = UpdateBuilder::new("example.net.".parse().unwrap()).build_message().unwrap();
+        assert_eq!(apply(&mut zone, &message), rcode::Rcode::NotAuth);
+    }
+
+    #[test]
+    fn it_reports_refused_by_policy() {
+        let mut zone = zone();
+        let message = update().delete_all("www.example.".parse().unwrap()).build_message().unwrap();
+        let read_only = |update: &Update| matches!(update, Update::Add(_));
+        assert_eq!(apply_with_policy(&mut zone, &message, read_only), rcode::Rcode::Refused);
+        assert!(zone.contains_name(&"www.example.".parse().unwrap()));
+    }
+
+    #[test]
+    fn it_protects_apex_and_cname_rules() {
+        let mut zone = zone();
+        let message = update()
+            .delete_all("example.".parse().unwrap())
+            .delete_record("example.".parse().unwrap(), rtype::Type::Ns, ns("ns1.example.").rdata)
+            .add_record(a("alias.example.", 1))
+            .add_record(record("www.example.", rtype::Type::Cname, RData::Cname("alias.example.".parse().unwrap())))
+            .build_message()
+            .unwrap();
+        assert_eq!(apply(&mut zone, &message), rcode::Rcode::NoError);
+        assert_eq!(zone.rrset(&"example.".parse().unwrap(), rtype::Type::Ns).count(), 1);
+        assert_eq!(zone.rrset(&"alias.example.".parse().unwrap(), rtype::Type::A).count(), 0);
+        assert_eq!(zone.rrset(&"www.example.".parse().unwrap(), rtype::Type::Cname).count(), 0);
+        assert_eq!(zone.soa().serial, 10);
+    }
+
+    #[test]
+    fn it_replaces_soa_with_newer_serial() {
+        let mut zone = zone();
+        let message = update().add_record(soa(20)).add_record(a("new.example.", 1)).build_message().unwrap();
+        assert_eq!(apply(&mut zone, &message), rcode::Rcode::NoError);
+        assert_eq!(zone.soa().serial, 20);
+
+        let message = update().add_record(soa(5)).build_message().unwrap();
+        assert_eq!(apply(&mut zone, &message), rcode::Rcode::NoError);
+        assert_eq!(zone.soa().serial, 20);
+    }
+}
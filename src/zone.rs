@@ -0,0 +1,181 @@
+use alloc::{vec, vec::Vec};
+use alloc::collections::BTreeMap;
+use crate::name::Name;
+use crate::record::rdata::{soa::Soa, RData};
+use crate::record::{class, rtype, Record};
+
+pub mod file;
+
+/// Authoritative data of a single zone, kept in memory and indexed by owner name.
+#[derive(Debug, Clone)]
+pub struct Zone {
+    origin: Name,
+    class:  class::Class,
+    nodes:  BTreeMap<Name, Vec<Record>>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FromError {
+    NotSoa,
+    OutOfZone(Name),
+    ClassMismatch(class::Class),
+}
+
+impl core::fmt::Display for FromError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotSoa               => write!(f, "zone must start from its SOA RR"),
+            Self::OutOfZone(name)      => write!(f, "{} is out of zone", name),
+            Self::ClassMismatch(class) => write!(f, "found record of other class: {:?}", class),
+        }
+    }
+}
+
+impl core::error::Error for FromError {}
+
+impl Zone {
+    /// Creates a zone whose origin and class are taken from the `soa` record.
+    pub fn new(soa: Record) -> Result<Self, FromError> {
+        if !matches!(soa.rdata, RData::Soa(_)) {
+            return Err(FromError::NotSoa);
+        }
+        let mut nodes = BTreeMap::new();
+        nodes.insert(soa.name.clone(), vec![]);
+        let mut zone = Self { origin: soa.name.clone(), class: soa.class, nodes };
+        zone.insert(soa)?;
+        Ok(zone)
+    }
+
+    pub fn origin(&self) -> &Name {
+        &self.origin
+    }
+
+    pub fn class(&self) -> class::Class {
+        self.class
+    }
+
+    pub fn soa(&self) -> &Soa {
+        self.records(&self.origin).iter()
+            .find_map(|record| match &record.rdata {
+                RData::Soa(soa) => Some(soa),
+                _ => None,
+            })
+            .expect("zone always keeps its SOA RR")
+    }
+
+    pub(crate) fn soa_mut(&mut self) -> &mut Soa {
+        self.nodes.get_mut(&self.origin)
+            .and_then(|records| records.iter_mut().find_map(|record| match &mut record.rdata {
+                RData::Soa(soa) => Some(soa),
+                _ => None,
+            }))
+            .expect("zone always keeps its SOA RR")
+    }
+
+    /// Adds `record`, replacing a record with the same owner, type and RDATA.
+    pub fn insert(&mut self, record: Record) -> Result<(), FromError> {
+        if !record.name.is_subdomain_of(&self.origin) {
+            return Err(FromError::OutOfZone(record.name));
+        }
+        if record.class != self.class {
+            return Err(FromError::ClassMismatch(record.class));
+        }
+        let records = self.nodes.entry(record.name.clone()).or_default();
+        match records.iter_mut().find(|existing| existing.rtype == record.rtype && existing.rdata == record.rdata) {
+            Some(existing) => *existing = record,
+            None           => records.push(record),
+        }
+        Ok(())
+    }
+
+    pub(crate) fn remove_where<Predicate>(&mut self, name: &Name, predicate: Predicate)
+        where Predicate: Fn(&Record) -> bool
+    {
+        if let Some(records) = self.nodes.get_mut(name) {
+            records.retain(|record| !predicate(record));
+            if records.is_empty() && name != &self.origin {
+                self.nodes.remove(name);
+            }
+        }
+    }
+
+    pub fn records(&self, name: &Name) -> &[Record] {
+        self.nodes.get(name).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn rrset<'a>(&'a self, name: &Name, rtype: rtype::Type) -> impl Iterator<Item = &'a Record> {
+        self.records(name).iter().filter(move |record| record.rtype == rtype)
+    }
+
+    pub fn contains_name(&self, name: &Name) -> bool {
+        !self.records(name).is_empty()
+    }
+
+    /// Every record in canonical order of owner names.
+    pub fn iter(&self) -> impl Iterator<Item = &Record> {
+        self.nodes.values().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn soa() -> Record {
+        Record {
+            name:  "example.".parse().unwrap(),
+            rtype: rtype::Type::Soa,
+            class: class::Class::In,
+            ttl:   3600,
+            rdata: RData::Soa(Soa {
+                mname:   "ns.example.".parse().unwrap(),
+                rname:   "hostmaster.example.".parse().unwrap(),
+                serial:  1,
+                refresh: 3600,
+                retry:   600,
+                expire:  604800,
+                minimum: 300,
+            }),
+        }
+    }
+
+    fn a(name: &str, ttl: u32) -> Record {
+        Record {
+            name:  name.parse().unwrap(),
+            rtype: rtype::Type::A,
+            class: class::Class::In,
+            ttl,
+            rdata: RData::A(core::net::Ipv4Addr::new(192, 0, 2, 1)),
+        }
+    }
+
+    #[test]
+    fn it_keeps_records_by_owner() {
+        let mut zone = Zone::new(soa()).unwrap();
+        assert_eq!(zone.origin(), &"example.".parse().unwrap());
+        assert_eq!(zone.soa().serial, 1);
+        zone.insert(a("www.example.", 300)).unwrap();
+        zone.insert(a("WWW.example.", 600)).unwrap();
+        let rrset: Vec<&Record> = zone.rrset(&"www.example.".parse().unwrap(), rtype::Type::A).collect();
+        assert_eq!(rrset, vec![&a("WWW.example.", 600)]);
+        assert_eq!(zone.iter().count(), 2);
+    }
+
+    #[test]
+    fn it_does_not_insert_foreign_records() {
+        let mut zone = Zone::new(soa()).unwrap();
+        assert_eq!(zone.insert(a("www.example.net.", 300)), Err(FromError::OutOfZone("www.example.net.".parse().unwrap())));
+        let chaos = Record { class: class::Class::Ch, ..a("www.example.", 300) };
+        assert_eq!(zone.insert(chaos), Err(FromError::ClassMismatch(class::Class::Ch)));
+        assert_eq!(Zone::new(a("example.", 300)).unwrap_err(), FromError::NotSoa);
+    }
+
+    #[test]
+    fn it_removes_records() {
+        let mut zone = Zone::new(soa()).unwrap();
+        zone.insert(a("www.example.", 300)).unwrap();
+        zone.remove_where(&"www.example.".parse().unwrap(), |_| true);
+        assert!(!zone.contains_name(&"www.example.".parse().unwrap()));
+    }
+}
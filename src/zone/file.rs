@@ -0,0 +1,392 @@
+use alloc::{boxed::Box, format, string::{String, ToString}, vec, vec::Vec};
+use crate::name::Name;
+use crate::record::parse::{self, Defaults, Entry, ParseError, Token, Tokens};
+use crate::record::{class, Record};
+
+/// Deepest nesting of `$INCLUDE`, which also stops files including each other.
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq)]
+pub enum FileError {
+    Parse(ParseError),
+    UnknownDirective { line: usize, directive: String },
+    /// `path` could not be read. `line` is 0 when it is the zone file itself.
+    Unreadable { line: usize, path: String },
+    IncludeTooDeep { line: usize },
+    /// `error` was found in the file included by `$INCLUDE` on `line`.
+    Included { line: usize, path: String, error: Box<FileError> },
+}
+
+impl FileError {
+    /// Line of the error, or of the `$INCLUDE` whose file the error was found in.
+    pub fn line(&self) -> usize {
+        match self {
+            Self::Parse(error)                                               => error.line(),
+            Self::UnknownDirective { line, .. } | Self::Unreadable { line, .. } => *line,
+            Self::IncludeTooDeep { line } | Self::Included { line, .. }         => *line,
+        }
+    }
+}
+
+impl core::fmt::Display for FileError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Parse(error)                         => write!(f, "{}", error),
+            Self::UnknownDirective { line, directive } => write!(f, "line {}: unknown directive {}", line, directive),
+            Self::Unreadable { line, path }            => write!(f, "line {}: cannot read {}", line, path),
+            Self::IncludeTooDeep { line }              => write!(f, "line {}: $INCLUDE is nested too deeply", line),
+            Self::Included { line, path, error }       => write!(f, "line {}: in {}: {}", line, path, error),
+        }
+    }
+}
+
+impl core::error::Error for FileError {}
+
+impl From<ParseError> for FileError {
+    fn from(error: ParseError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+/// What earlier entries leave for the following ones (RFC 1035 §5.1).
+struct State {
+    origin:      Name,
+    owner:       Option<Name>,
+    /// TTL set by `$TTL` (RFC 2308 §4).
+    default_ttl: Option<u32>,
+    last_ttl:    Option<u32>,
+    class:       Option<class::Class>,
+}
+
+impl State {
+    fn defaults(&self) -> Defaults<'_> {
+        Defaults {
+            origin: &self.origin,
+            owner:  self.owner.as_ref(),
+            ttl:    self.default_ttl.or(self.last_ttl),
+            class:  self.class,
+        }
+    }
+
+    fn push(&mut self, record: Record, records: &mut Vec<Record>) {
+        self.owner = Some(record.name.clone());
+        self.last_ttl = Some(record.ttl);
+        self.class = Some(record.class);
+        records.push(record);
+    }
+}
+
+/// Parses a zone file in master-file syntax (RFC 1035 §5), with relative names completed by `origin`
+/// until `$ORIGIN` changes it. `$INCLUDE` is reported as unreadable.
+pub fn parse(text: &str, origin: &Name) -> Result<Vec<Record>, FileError> {
+    parse_with_includes(text, origin, |_| None)
+}
+
+/// Parses a zone file like [`parse`], reading the files of `$INCLUDE` with `include`.
+pub fn parse_with_includes<Include>(text: &str, origin: &Name, mut include: Include) -> Result<Vec<Record>, FileError>
+    where Include: FnMut(&str) -> Option<String>
+{
+    let mut state = State { origin: origin.clone(), owner: None, default_ttl: None, last_ttl: None, class: None };
+    let mut records = vec![];
+    parse_into(text, &mut state, &mut include, 0, &mut records)?;
+    Ok(records)
+}
+
+/// Reads and parses the zone file at `path`, taking the paths of `$INCLUDE` relative to its directory.
+#[cfg(feature = "std")]
+pub fn load(path: impl AsRef<std::path::Path>, origin: &Name) -> Result<Vec<Record>, FileError> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path)
+        .map_err(|_| FileError::Unreadable { line: 0, path: path.display().to_string() })?;
+    let directory = path.parent().unwrap_or(std::path::Path::new(""));
+    parse_with_includes(&text, origin, |included| std::fs::read_to_string(directory.join(included)).ok())
+}
+
+fn parse_into(
+    text:    &str,
+    state:   &mut State,
+    include: &mut dyn FnMut(&str) -> Option<String>,
+    depth:   usize,
+    records: &mut Vec<Record>,
+) -> Result<(), FileError> {
+    for entry in parse::entries(text) {
+        let entry = entry?;
+        let directive = entry.tokens[0];
+        if entry.indented || directive.quoted || !directive.text.starts_with('$') {
+            let record = parse::parse_entry(&entry, &state.defaults())?;
+            state.push(record, records);
+            continue;
+        }
+
+        let mut tokens = Tokens::new(&entry);
+        tokens.next("directive")?;
+        match directive.text.to_ascii_uppercase().as_str() {
+            "$ORIGIN" => {
+                state.origin = tokens.name(&state.origin)?;
+                tokens.finish()?;
+            },
+            "$TTL" => {
+                state.default_ttl = Some(tokens.ttl("TTL")?);
+                tokens.finish()?;
+            },
+            "$INCLUDE" => {
+                let path = tokens.next("file name")?.text;
+                let origin = match tokens.peek() {
+                    Some(_) => tokens.name(&state.origin)?,
+                    None    => state.origin.clone(),
+                };
+                tokens.finish()?;
+                if depth == MAX_INCLUDE_DEPTH {
+                    return Err(FileError::IncludeTooDeep { line: entry.line });
+                }
+                let included = include(path)
+                    .ok_or_else(|| FileError::Unreadable { line: entry.line, path: path.to_string() })?;
+
+                // the origin and owner are restored once the file has been read (RFC 1035 §5.1)
+                let origin = core::mem::replace(&mut state.origin, origin);
+                let owner = state.owner.clone();
+                parse_into(&included, state, include, depth + 1, records)
+                    .map_err(|error| FileError::Included { line: entry.line, path: path.to_string(), error: Box::new(error) })?;
+                state.origin = origin;
+                state.owner = owner;
+            },
+            "$GENERATE" => generate(&entry, &mut tokens, state, records)?,
+            _ => return Err(FileError::UnknownDirective { line: entry.line, directive: directive.text.to_string() }),
+        }
+    }
+    Ok(())
+}
+
+/// Expands `$GENERATE range lhs [ttl] [class] type rhs`, as introduced by BIND.
+fn generate(entry: &Entry, tokens: &mut Tokens, state: &mut State, records: &mut Vec<Record>) -> Result<(), FileError> {
+    let range = tokens.next("range")?;
+    let (start, stop, step) = parse_range(range.text).ok_or_else(|| range.invalid("range"))?;
+    let lhs = tokens.next("owner")?;
+    let mut middle = vec![];
+    while let Some(token) = tokens.peek() {
+        tokens.next("RDATA")?;
+        middle.push(token);
+    }
+    let rhs = middle.pop().ok_or(ParseError::Missing { line: entry.line, expected: "RDATA" })?;
+
+    for value in (start..=stop).step_by(step) {
+        let owner = substitute(lhs.text, value).ok_or_else(|| lhs.invalid("owner"))?;
+        let rdata = substitute(rhs.text, value).ok_or_else(|| rhs.invalid("RDATA"))?;
+        let mut generated = vec![Token { text: &owner, ..lhs }];
+        generated.extend_from_slice(&middle);
+        generated.push(Token { text: &rdata, ..rhs });
+        let generated = Entry { line: entry.line, indented: false, tokens: generated };
+        let record = parse::parse_entry(&generated, &state.defaults())?;
+        state.push(record, records);
+    }
+    Ok(())
+}
+
+/// Parses `start-stop[/step]`.
+fn parse_range(range: &str) -> Option<(u32, u32, usize)> {
+    let (range, step) = match range.split_once('/') {
+        Some((range, step)) => (range, parse_number(step)?),
+        None                => (range, 1),
+    };
+    let (start, stop) = range.split_once('-')?;
+    let (start, stop) = (parse_number(start)?, parse_number(stop)?);
+    if start > stop || step == 0 {
+        return None;
+    }
+    Some((start, stop, step as usize))
+}
+
+fn parse_number(text: &str) -> Option<u32> {
+    // u32::from_str would also take a sign
+    if text.is_empty() || !text.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+/// Replaces `$` and `${offset,width,base}` with `value`, keeping `\$` as a `$`.
+fn substitute(template: &str, value: u32) -> Option<String> {
+    let mut text = String::new();
+    let mut rest = template;
+    while let Some(at) = rest.find(['$', '\\']) {
+        text.push_str(&rest[..at]);
+        if rest[at..].starts_with("\\$") {
+            text.push('$');
+            rest = &rest[at + 2..];
+            continue;
+        }
+        if rest[at..].starts_with('\\') {
+            // other escapes are left to the name and RDATA parsers
+            let escaped = rest[at..].chars().take(2).map(char::len_utf8).sum::<usize>();
+            text.push_str(&rest[at..at + escaped]);
+            rest = &rest[at + escaped..];
+            continue;
+        }
+        rest = &rest[at + 1..];
+        let (offset, width, base) = match rest.strip_prefix('{') {
+            Some(modifiers) => {
+                let (modifiers, after) = modifiers.split_once('}')?;
+                rest = after;
+                parse_modifiers(modifiers)?
+            },
+            None => (0, 0, 'd'),
+        };
+        let value = value.checked_add_signed(offset)?;
+        let formatted = match base {
+            'd' => format!("{:0width$}", value, width = width),
+            'o' => format!("{:0width$o}", value, width = width),
+            'x' => format!("{:0width$x}", value, width = width),
+            'X' => format!("{:0width$X}", value, width = width),
+            _   => return None,
+        };
+        text.push_str(&formatted);
+    }
+    text.push_str(rest);
+    Some(text)
+}
+
+/// Parses `offset[,width[,base]]` of a `$GENERATE` substitution.
+fn parse_modifiers(modifiers: &str) -> Option<(i32, usize, char)> {
+    let mut fields = modifiers.split(',');
+    let offset = fields.next()?;
+    let offset = match offset.strip_prefix('-') {
+        Some(magnitude) => -i32::try_from(parse_number(magnitude)?).ok()?,
+        None            => i32::try_from(parse_number(offset.strip_prefix('+').unwrap_or(offset))?).ok()?,
+    };
+    let width = fields.next().map(parse_number).unwrap_or(Some(0))? as usize;
+    let base = match fields.next() {
+        Some(base) => {
+            let mut chars = base.chars();
+            let base = chars.next()?;
+            if chars.next().is_some() {
+                return None;
+            }
+            base
+        },
+        None => 'd',
+    };
+    if fields.next().is_some() {
+        return None;
+    }
+    Some((offset, width, base))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::net::Ipv4Addr;
+    use crate::record::rdata::RData;
+    use crate::record::rtype;
+    use pretty_assertions::assert_eq;
+
+    fn origin() -> Name {
+        "example.".parse().unwrap()
+    }
+
+    fn a(name: &str, ttl: u32, address: Ipv4Addr) -> Record {
+        Record {
+            name:  name.parse().unwrap(),
+            rtype: rtype::Type::A,
+            class: class::Class::In,
+            ttl,
+            rdata: RData::A(address),
+        }
+    }
+
+    #[test]
+    fn it_parses_zone_file() {
+        let text = "\
+$TTL 1h
+@       IN  SOA ns hostmaster ( 1 ; serial
+                    1d 2h 4w 5m )
+        IN  NS  ns
+ns      600 A   192.0.2.1
+            A   192.0.2.2 ; previous owner, $TTL
+$ORIGIN sub.example.
+www         A   192.0.2.3
+";
+        let records = parse(text, &origin()).unwrap();
+        assert_eq!(records.len(), 5);
+        assert_eq!((records[0].rtype, records[0].ttl), (rtype::Type::Soa, 3_600));
+        assert_eq!(records[1].name, origin());
+        assert_eq!(records[1].rdata, RData::Ns("ns.example.".parse().unwrap()));
+        assert_eq!(records[2..], [
+            a("ns.example.",      600,   Ipv4Addr::new(192, 0, 2, 1)),
+            a("ns.example.",      3_600, Ipv4Addr::new(192, 0, 2, 2)),
+            a("www.sub.example.", 3_600, Ipv4Addr::new(192, 0, 2, 3)),
+        ]);
+    }
+
+    #[test]
+    fn it_inherits_last_ttl_without_default() {
+        let records = parse("a 300 A 192.0.2.1\nb A 192.0.2.2\n", &origin()).unwrap();
+        assert_eq!(records[1], a("b.example.", 300, Ipv4Addr::new(192, 0, 2, 2)));
+    }
+
+    #[test]
+    fn it_includes_files() {
+        let text = "$TTL 60\n$INCLUDE hosts sub\nwww A 192.0.2.1\n";
+        let records = parse_with_includes(text, &origin(), |path| match path {
+            "hosts" => Some("$ORIGIN other.\nhost A 192.0.2.2\n".to_string()),
+            _       => None,
+        }).unwrap();
+        assert_eq!(records, vec![
+            a("host.other.",  60, Ipv4Addr::new(192, 0, 2, 2)),
+            a("www.example.", 60, Ipv4Addr::new(192, 0, 2, 1)),
+        ]);
+
+        let records = parse_with_includes("$TTL 60\n$INCLUDE hosts sub\n", &origin(), |_| Some("host A 192.0.2.2\n".to_string()));
+        assert_eq!(records, Ok(vec![a("host.sub.example.", 60, Ipv4Addr::new(192, 0, 2, 2))]));
+    }
+
+    #[test]
+    fn it_generates_records() {
+        let text = "$TTL 60\n$GENERATE 1-5/2 host-${10,3} A 192.0.2.$\n$GENERATE 0-0 \\$x${0,2,X} CNAME x\n";
+        let records = parse(text, &origin()).unwrap();
+        assert_eq!(records[..3], [
+            a("host-011.example.", 60, Ipv4Addr::new(192, 0, 2, 1)),
+            a("host-013.example.", 60, Ipv4Addr::new(192, 0, 2, 3)),
+            a("host-015.example.", 60, Ipv4Addr::new(192, 0, 2, 5)),
+        ]);
+        assert_eq!(records[3].name, "$x00.example.".parse().unwrap());
+    }
+
+    #[test]
+    fn it_reports_errors_with_line() {
+        let error = parse("$TTL 60\n\nwww A 192.0.2\n", &origin()).unwrap_err();
+        assert_eq!(error.line(), 3);
+        assert_eq!(error.to_string(), "line 3: invalid IPv4 address \"192.0.2\"");
+
+        let text = "$TTL 60\n@ SOA ns hostmaster (\n 1 1d\n 2x 4w 1h )\n";
+        assert_eq!(
+            parse(text, &origin()),
+            Err(FileError::Parse(ParseError::Invalid { line: 4, expected: "retry", token: "2x".to_string() })),
+        );
+        assert_eq!(
+            parse("$TTL 60\n@ MX ( 10\n mail extra )\n", &origin()),
+            Err(FileError::Parse(ParseError::TrailingTokens { line: 3, token: "extra".to_string() })),
+        );
+
+        assert_eq!(
+            parse("$TTL 60\n  A 192.0.2.1\n", &origin()),
+            Err(FileError::Parse(ParseError::Missing { line: 2, expected: "owner" })),
+        );
+        assert_eq!(
+            parse("$UNKNOWN x\n", &origin()),
+            Err(FileError::UnknownDirective { line: 1, directive: "$UNKNOWN".to_string() }),
+        );
+        assert_eq!(
+            parse("\n$INCLUDE missing\n", &origin()),
+            Err(FileError::Unreadable { line: 2, path: "missing".to_string() }),
+        );
+        assert_eq!(
+            parse_with_includes("$INCLUDE self\n", &origin(), |_| Some("$INCLUDE self\n".to_string())).unwrap_err().to_string(),
+            "line 1: in self: ".repeat(MAX_INCLUDE_DEPTH) + "line 1: $INCLUDE is nested too deeply",
+        );
+        assert_eq!(
+            parse("$GENERATE 5-1 a A 192.0.2.1\n", &origin()),
+            Err(FileError::Parse(ParseError::Invalid { line: 1, expected: "range", token: "5-1".to_string() })),
+        );
+    }
+}